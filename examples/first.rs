@@ -45,6 +45,7 @@ impl Sandbox for MyApp {
             theme: PlotThemeSettings {
                 line_color: Color::from_rgb8(0, 200, 0),
                 point_color: Color::from_rgb8(0, 200, 0),
+                ..Default::default()
             },
             ..Default::default()
         };
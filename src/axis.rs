@@ -0,0 +1,93 @@
+//Returns `n` evenly spaced values from `min` to `max` (both inclusive).
+//Useful for generating x values when plotting a function.
+pub fn linspace(min: f32, max: f32, n: usize) -> Vec<f32> {
+    match n {
+        0 => Vec::new(),
+        1 => vec![min],
+        _ => {
+            let step = (max - min) / (n - 1) as f32;
+            (0..n)
+                .map(|i| if i == n - 1 { max } else { min + step * i as f32 })
+                .collect()
+        }
+    }
+}
+
+//Returns values from `start` (inclusive) to `end` (exclusive) with the given `step`, step > 0
+pub fn range_step(start: i64, end: i64, step: i64) -> Vec<i64> {
+    assert!(step > 0, "Step must be positive!");
+    let mut result = Vec::new();
+    let mut value = start;
+    while value < end {
+        result.push(value);
+        value = match value.checked_add(step) {
+            Some(value) => value,
+            None => break,
+        };
+    }
+    result
+}
+
+//Returns bounds around `[min; max]` padded by `padding_frac` of the range on both sides
+//and rounded outwards to a nice step (1, 2 or 5 times a power of ten).
+//Padding never makes a range cross zero, and a range of zero length is widened around its value.
+pub fn nice_bounds(min: f32, max: f32, padding_frac: f32) -> (f32, f32) {
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+    let span = if max > min {
+        max - min
+    } else if min != 0.0 {
+        min.abs()
+    } else {
+        1.0
+    };
+    let padding = span * padding_frac.max(0.0);
+    let mut lo = min - padding;
+    let mut hi = max + padding;
+    if max == min && padding == 0.0 {
+        lo -= span / 2.0;
+        hi += span / 2.0;
+    }
+    if min >= 0.0 && max > 0.0 {
+        lo = lo.max(0.0);
+    }
+    if max <= 0.0 && min < 0.0 {
+        hi = hi.min(0.0);
+    }
+    let step = nice_step((hi - lo) / 5.0);
+    ((lo / step).floor() * step, (hi / step).ceil() * step)
+}
+
+//The smallest value of 1, 2 or 5 times a power of ten which is at least `raw_step`
+fn nice_step(raw_step: f32) -> f32 {
+    let magnitude = 10f32.powf(raw_step.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(10.0 * magnitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linspace_steps_evenly() {
+        let expected: Vec<f32> = (0..=10).map(|i| i as f32).collect();
+        assert_eq!(linspace(0.0, 10.0, 11), expected);
+        assert_eq!(linspace(0.0, 10.0, 0), Vec::<f32>::new());
+        assert_eq!(linspace(3.0, 10.0, 1), vec![3.0]);
+        assert_eq!(range_step(0, 10, 3), vec![0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn nice_bounds_round_outwards() {
+        assert_eq!(nice_bounds(0.0, 9.3, 0.0), (0.0, 10.0));
+        assert_eq!(nice_bounds(12.0, 87.0, 0.1), (0.0, 100.0));
+        //All negative ranges don't cross zero either
+        assert_eq!(nice_bounds(-9.3, -0.7, 0.0), (-10.0, 0.0));
+        //A range of zero length is widened around its value
+        assert_eq!(nice_bounds(5.0, 5.0, 0.0), (2.0, 8.0));
+        assert_eq!(nice_bounds(9.3, 0.0, 0.0), (0.0, 10.0));
+    }
+}
@@ -1,10 +1,27 @@
 pub mod data;
+pub mod facet;
+pub mod format;
+#[cfg(feature = "export")]
+pub mod svg;
+pub mod text;
+#[cfg(test)]
+mod tests;
 
-use iced::{Point, Rectangle, Size, Vector};
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+use std::time::Instant;
+
+use iced::{Color, Point, Rectangle, Size, Vector};
 use iced::canvas::{Cache, Cursor, Frame, Geometry, Path, Program, Stroke, Text};
 use iced::{HorizontalAlignment, VerticalAlignment};
 
-use self::data::{AxisData, AxisValue, PlotSettings, Settings};
+use self::data::{AxisData, AxisValue, CoincidentPolicy, CrosshairSettings, DistanceValue, GridLayer, InteractionEvent, LabelOverflow, LegendSettings, LineStyle, Marker, NumberFormat, PlotSettings, PlotThemeSettings, PointSelection, ReadoutMode, ScaleKind, SelectionEmphasis, Settings, StreamingBounds, TooltipPlacement, ValidationWarning, ViewState, YAxis};
 
 pub struct ChartBuilder<
     XV: AxisValue,
@@ -18,6 +35,21 @@ pub struct ChartBuilder<
     min_y_value_opt: Option<YV>,
     max_y_value_opt: Option<YV>,
     data: Vec<(PlotSettings, Vec<(XD, YD)>)>,
+    x_labeled_ticks: Vec<(XV, String)>,
+    x_tick_values_opt: Option<Vec<XV>>,
+    y_tick_values_opt: Option<Vec<YV>>,
+    confidence_ellipses: Vec<ConfidenceEllipse<XV, YV>>,
+    crossing_reference_opt: Option<YV>,
+    interaction_sender_opt: Option<Sender<InteractionEvent<XV, YV>>>,
+    normal_band_opt: Option<(YV, YV, Color)>,
+    h_reference_lines: Vec<(YV, Color, String)>, //Value, color and label (empty for none)
+    x_display_transform_opt: Option<Box<dyn Fn(&XV) -> XV>>,
+    y_display_transform_opt: Option<Box<dyn Fn(&YV) -> YV>>,
+    x_label_formatter_opt: Option<Arc<dyn Fn(&XV) -> String>>,
+    y_label_formatter_opt: Option<Arc<dyn Fn(&YV) -> String>>,
+    downsample_target_opt: Option<usize>,
+    secondary_y_values_opt: Option<(YV, YV)>,
+    tooltip_formatter_opt: Option<Arc<dyn Fn(&XD, &YD) -> String>>,
 }
 
 impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBuilder<XV, YV, XD, YD> {
@@ -29,6 +61,21 @@ impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBui
             min_y_value_opt: None,
             max_y_value_opt: None,
             data: Vec::new(),
+            x_labeled_ticks: Vec::new(),
+            x_tick_values_opt: None,
+            y_tick_values_opt: None,
+            confidence_ellipses: Vec::new(),
+            crossing_reference_opt: None,
+            interaction_sender_opt: None,
+            normal_band_opt: None,
+            h_reference_lines: Vec::new(),
+            x_display_transform_opt: None,
+            y_display_transform_opt: None,
+            x_label_formatter_opt: None,
+            y_label_formatter_opt: None,
+            downsample_target_opt: None,
+            secondary_y_values_opt: None,
+            tooltip_formatter_opt: None,
         }
     }
 
@@ -42,15 +89,69 @@ impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBui
         let max_x_value = self.max_x_value_opt.unwrap();
         let min_y_value = self.min_y_value_opt.unwrap();
         let max_y_value = self.max_y_value_opt.unwrap();
-        let data = self.data;
-        Chart::new(
+        let data = match self.downsample_target_opt {
+            Some(target) => self.data.into_iter().map(|(plot_settings, edges)| (plot_settings, downsampled(edges, target))).collect(),
+            None => self.data,
+        };
+        let mut chart = Chart::new(
             settings,
             min_x_value,
             max_x_value,
             min_y_value,
             max_y_value,
             data,
-        )
+        );
+        chart.x_labeled_ticks = self.x_labeled_ticks;
+        chart.x_tick_values_opt = self.x_tick_values_opt;
+        chart.y_tick_values_opt = self.y_tick_values_opt;
+        chart.confidence_ellipses = self.confidence_ellipses;
+        chart.crossing_reference_opt = self.crossing_reference_opt;
+        chart.interaction_sender_opt = self.interaction_sender_opt;
+        chart.normal_band_opt = self.normal_band_opt;
+        chart.h_reference_lines = self.h_reference_lines;
+        chart.x_display_transform_opt = self.x_display_transform_opt;
+        chart.y_display_transform_opt = self.y_display_transform_opt;
+        chart.x_label_formatter_opt = self.x_label_formatter_opt;
+        chart.y_label_formatter_opt = self.y_label_formatter_opt;
+        chart.tooltip_formatter_opt = self.tooltip_formatter_opt;
+        chart.secondary_y_range_opt = self.secondary_y_values_opt.map(|(min_y_value, max_y_value)| {
+            let total_y_distance = min_y_value.distance_to(&max_y_value);
+            (min_y_value, max_y_value, total_y_distance)
+        });
+        chart
+    }
+
+    //Checks the data for problems which make the chart look wrong, without building it.
+    //Bounds are checked only if they were already set or calculated.
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+        for (plot_index, (_plot_settings, edges)) in self.data.iter().enumerate() {
+            if edges.is_empty() {
+                warnings.push(ValidationWarning::EmptyPlot { plot_index });
+                continue;
+            }
+            for (point_index, (xd, yd)) in edges.iter().enumerate() {
+                if !xd.value().is_finite() || !yd.value().is_finite() {
+                    warnings.push(ValidationWarning::NonFiniteValue { plot_index, point_index });
+                } else if self.is_out_of_bounds(xd.value(), yd.value()) {
+                    warnings.push(ValidationWarning::OutOfBounds { plot_index, point_index });
+                }
+            }
+            for (index, slice) in edges.windows(2).enumerate() {
+                let point_index = index + 1;
+                match slice[0].0.value().compare_value(slice[1].0.value()) {
+                    Ordering::Greater => warnings.push(ValidationWarning::UnsortedX { plot_index, point_index }),
+                    Ordering::Equal => warnings.push(ValidationWarning::DuplicateX { plot_index, point_index }),
+                    Ordering::Less => {}
+                }
+            }
+        }
+        warnings
+    }
+
+    fn is_out_of_bounds(&self, xv: &XV, yv: &YV) -> bool {
+        is_outside(xv, &self.min_x_value_opt, &self.max_x_value_opt)
+            || is_outside(yv, &self.min_y_value_opt, &self.max_y_value_opt)
     }
 
     pub fn data(mut self, data: Vec<(PlotSettings, Vec<(XD, YD)>)>) -> Self {
@@ -58,11 +159,198 @@ impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBui
         self
     }
 
+    //Same as `data`, but the plots are keyed by their settings.
+    //They are ordered by name, then by a hash of the settings which is the same on every run, so they are drawn the same way.
+    pub fn data_map(mut self, data: HashMap<PlotSettings, Vec<(XD, YD)>>) -> Self {
+        let stable_hash = |plot_settings: &PlotSettings| {
+            let mut hasher = DefaultHasher::new();
+            plot_settings.hash(&mut hasher);
+            hasher.finish()
+        };
+        let mut data: Vec<(PlotSettings, Vec<(XD, YD)>)> = data.into_iter().collect();
+        data.sort_by_cached_key(|(plot_settings, _edges)| (plot_settings.name.clone(), stable_hash(plot_settings)));
+        self.data = data;
+        self
+    }
+
     pub fn add_data(mut self, plot_settings: PlotSettings, edges: Vec<(XD, YD)>) -> Self {
         self.data.push((plot_settings, edges));
         self
     }
 
+    //Drops the plot with the given index, out of range indices are ignored.
+    //The bounds stay, `calculate_*` afterwards only counts the remaining plots.
+    pub fn remove_data(mut self, index: usize) -> Self {
+        if index < self.data.len() {
+            self.data.remove(index);
+        }
+        self
+    }
+
+    //Drops every plot, the bounds stay
+    pub fn clear_data(mut self) -> Self {
+        self.data.clear();
+        self
+    }
+
+    //Replaces the given plots with one plot of all of their points sorted by x, placed where the first of them was.
+    //Points with the same x are all kept, in the order of their plots. Out of range indices are ignored.
+    pub fn merge_plots(mut self, indices: &[usize], plot_settings: PlotSettings) -> Self {
+        let mut indices: Vec<usize> = indices.iter().copied().filter(|index| *index < self.data.len()).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        if let Some(position) = indices.first().copied() {
+            let mut merged: Vec<(XD, YD)> = indices
+                .iter()
+                .flat_map(|index| self.data[*index].1.iter().cloned())
+                .collect();
+            merged.sort_by(|(xd1, _yd1), (xd2, _yd2)| xd1.value().compare_value(xd2.value()));
+            for index in indices.iter().rev() {
+                self.data.remove(*index);
+            }
+            self.data.insert(position, (plot_settings, merged));
+        }
+        self
+    }
+
+    //Reduces every plot to at most `target` points with Largest-Triangle-Three-Buckets when the chart is built,
+    //keeping the first and the last point. Plots with fewer points stay as they are.
+    pub fn downsample(mut self, target: usize) -> Self {
+        self.downsample_target_opt = Some(target);
+        self
+    }
+
+    //Sorts the points of the plots added so far by x, points with the same x keep their order
+    pub fn sort_by_x(mut self) -> Self {
+        for (_plot_settings, edges) in self.data.iter_mut() {
+            edges.sort_by(|(xd1, _yd1), (xd2, _yd2)| xd1.value().compare_value(xd2.value()));
+        }
+        self
+    }
+
+    //Colors the plots added so far along a gradient by their index: the first plot gets `from`, the last one `to`
+    pub fn gradient_series_colors(mut self, from: Color, to: Color) -> Self {
+        let count = self.data.len();
+        for (index, (plot_settings, _edges)) in self.data.iter_mut().enumerate() {
+            let t = if count > 1 { index as f32 / (count - 1) as f32 } else { 0.0 };
+            let color = crate::math::interpolate_color(from, to, t);
+            plot_settings.theme.line_color = color;
+            plot_settings.theme.point_color = color;
+        }
+        self
+    }
+
+    //Adds the confidence ellipse (`confidence` in `(0;1)`, e.g. 0.95) of the points of the given plot.
+    //Plots with fewer than two points get no ellipse.
+    pub fn add_confidence_ellipse(mut self, plot_index: usize, confidence: f32, theme: PlotThemeSettings) -> Self {
+        if let Some((_plot_settings, edges)) = self.data.get(plot_index) {
+            //Distances are measured from the smallest values, so they are never negative
+            let min_x_opt = edges.iter().map(|(xd, _yd)| xd.value()).min_by(|xv1, xv2| xv1.compare_value(xv2));
+            let min_y_opt = edges.iter().map(|(_xd, yd)| yd.value()).min_by(|yv1, yv2| yv1.compare_value(yv2));
+            if let (Some(min_x), Some(min_y)) = (min_x_opt, min_y_opt) {
+                let distances: Vec<(f32, f32)> = edges
+                    .iter()
+                    .map(|(xd, yd)| (min_x.distance_to(xd.value()), min_y.distance_to(yd.value())))
+                    .collect();
+                if let Some((center, semi_axes, angle)) = crate::math::confidence_ellipse(&distances, confidence) {
+                    let ellipse = ConfidenceEllipse {
+                        reference: (min_x.clone(), min_y.clone()),
+                        center,
+                        semi_axes,
+                        angle,
+                        theme,
+                    };
+                    self.confidence_ellipses.push(ellipse);
+                }
+            }
+        }
+        self
+    }
+
+    //Forwards hovers and clicks inside of the margined area to `sender`, e.g. to log or replay them.
+    //Sending stops silently once the receiver is dropped.
+    pub fn interaction_events(mut self, sender: Sender<InteractionEvent<XV, YV>>) -> Self {
+        self.interaction_sender_opt = Some(sender);
+        self
+    }
+
+    //Converts x values before they are displayed in labels and descriptions (e.g. bytes to megabytes),
+    //the points are still placed by their original values
+    pub fn x_display_transform(mut self, transform: impl Fn(&XV) -> XV + 'static) -> Self {
+        self.x_display_transform_opt = Some(Box::new(transform));
+        self
+    }
+
+    //Same as `x_display_transform`, but for y values
+    pub fn y_display_transform(mut self, transform: impl Fn(&YV) -> YV + 'static) -> Self {
+        self.y_display_transform_opt = Some(Box::new(transform));
+        self
+    }
+
+    //Formats x values in labels and descriptions instead of `XD::display_value`
+    pub fn x_label_formatter(mut self, formatter: impl Fn(&XV) -> String + 'static) -> Self {
+        self.x_label_formatter_opt = Some(Arc::new(formatter));
+        self
+    }
+
+    //Formats y values in labels and descriptions instead of `YD::display_value`
+    pub fn y_label_formatter(mut self, formatter: impl Fn(&YV) -> String + 'static) -> Self {
+        self.y_label_formatter_opt = Some(Arc::new(formatter));
+        self
+    }
+
+    //Text of the tooltip of a point instead of its x and y descriptions on two lines
+    pub fn tooltip_formatter(mut self, formatter: impl Fn(&XD, &YD) -> String + 'static) -> Self {
+        self.tooltip_formatter_opt = Some(Arc::new(formatter));
+        self
+    }
+
+    //Shades the y range between `low` and `high` across the whole width, behind the data
+    pub fn normal_band(mut self, low: YV, high: YV, color: Color) -> Self {
+        self.normal_band_opt = Some((low, high, color));
+        self
+    }
+
+    //Draws a horizontal line across the whole width at the given y value (e.g. an alert level), behind the data.
+    //The label is written above the line at its left end, an empty label is left out.
+    //Lines with a value outside of the y bounds aren't drawn.
+    pub fn h_reference_line(mut self, value: YV, color: Color, label: impl Into<String>) -> Self {
+        self.h_reference_lines.push((value, color, label.into()));
+        self
+    }
+
+    //Marks the points where lines cross the given y value (e.g. zero)
+    pub fn mark_crossings(mut self, reference: YV) -> Self {
+        self.crossing_reference_opt = Some(reference);
+        self
+    }
+
+    //Ticks drawn as x labels with their own text, in addition to the calculated ones.
+    //They are set here and not in `Settings` because `Settings` doesn't know the type of the x values,
+    //like the bounds and `x_tick_values`.
+    pub fn x_labeled_ticks(mut self, x_labeled_ticks: Vec<(XV, String)>) -> Self {
+        self.x_labeled_ticks = x_labeled_ticks;
+        self
+    }
+
+    pub fn add_x_labeled_tick(mut self, xv: XV, text: String) -> Self {
+        self.x_labeled_ticks.push((xv, text));
+        self
+    }
+
+    //Labels the x axis exactly at these values instead of the calculated ones (and the bounds).
+    //Values outside of the bounds are left out.
+    pub fn x_tick_values(mut self, xvs: Vec<XV>) -> Self {
+        self.x_tick_values_opt = Some(xvs);
+        self
+    }
+
+    //Same as `x_tick_values`, but for the primary y axis
+    pub fn y_tick_values(mut self, yvs: Vec<YV>) -> Self {
+        self.y_tick_values_opt = Some(yvs);
+        self
+    }
+
     pub fn min_x_value(mut self, min_x_value: XV) -> Self {
         self.min_x_value_opt = Some(min_x_value);
         self
@@ -90,6 +378,7 @@ impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBui
             .iter()
             .map(|(_settings, vec)| vec)
             .flat_map(|vec| vec.iter().map(|(xv, _yv)| xv.value()))
+            .filter(|xv| xv.is_finite())
             .min_by(|xv1, xv2| xv1.compare_value(xv2))
             .unwrap()
             .clone();
@@ -104,6 +393,7 @@ impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBui
             .iter()
             .map(|(_settings, vec)| vec)
             .flat_map(|vec| vec.iter().map(|(xv, _yv)| xv.value()))
+            .filter(|xv| xv.is_finite())
             .max_by(|xv1, xv2| xv1.compare_value(xv2))
             .unwrap()
             .clone();
@@ -111,13 +401,16 @@ impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBui
         self
     }
 
+    //Only the plots of the primary y axis count, see `calculate_secondary_y_values`
     pub fn calculate_min_y_value(mut self) -> Self {
-        assert!(self.data.iter().any(|(_settings, vec)| !vec.is_empty()));
+        assert!(self.data.iter().any(|(settings, vec)| settings.axis == YAxis::Primary && !vec.is_empty()));
         let min_y_value = self
             .data
             .iter()
+            .filter(|(settings, _vec)| settings.axis == YAxis::Primary)
             .map(|(_settings, vec)| vec)
             .flat_map(|vec| vec.iter().map(|(_xv, yv)| yv.value()))
+            .filter(|yv| yv.is_finite())
             .min_by(|yv1, yv2| yv1.compare_value(yv2))
             .unwrap()
             .clone();
@@ -125,13 +418,16 @@ impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBui
         self
     }
 
+    //Only the plots of the primary y axis count, see `calculate_secondary_y_values`
     pub fn calculate_max_y_value(mut self) -> Self {
-        assert!(self.data.iter().any(|(_settings, vec)| !vec.is_empty()));
+        assert!(self.data.iter().any(|(settings, vec)| settings.axis == YAxis::Primary && !vec.is_empty()));
         let max_y_value = self
             .data
             .iter()
+            .filter(|(settings, _vec)| settings.axis == YAxis::Primary)
             .map(|(_settings, vec)| vec)
             .flat_map(|vec| vec.iter().map(|(_xv, yv)| yv.value()))
+            .filter(|yv| yv.is_finite())
             .max_by(|yv1, yv2| yv1.compare_value(yv2))
             .unwrap()
             .clone();
@@ -153,6 +449,123 @@ impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> ChartBui
         self.calculate_min_max_x_values()
             .calculate_min_max_y_values()
     }
+
+    //Range of the secondary y axis, used by the plots with `YAxis::Secondary`
+    pub fn secondary_y_values(mut self, min_y_value: YV, max_y_value: YV) -> Self {
+        self.secondary_y_values_opt = Some((min_y_value, max_y_value));
+        self
+    }
+
+    //Sets the range of the secondary y axis to the values of its plots
+    pub fn calculate_secondary_y_values(mut self) -> Self {
+        assert!(
+            self.data.iter().any(|(settings, vec)| {
+                settings.axis == YAxis::Secondary && vec.iter().any(|(_xv, yv)| yv.value().is_finite())
+            }),
+            "There is no finite y value on the secondary axis!"
+        );
+        let yvs = || {
+            self.data
+                .iter()
+                .filter(|(settings, _vec)| settings.axis == YAxis::Secondary)
+                .flat_map(|(_settings, vec)| vec.iter().map(|(_xv, yv)| yv.value()))
+                .filter(|yv| yv.is_finite())
+        };
+        let min_y_value = yvs().min_by(|yv1, yv2| yv1.compare_value(yv2)).unwrap().clone();
+        let max_y_value = yvs().max_by(|yv1, yv2| yv1.compare_value(yv2)).unwrap().clone();
+        self.secondary_y_values_opt = Some((min_y_value, max_y_value));
+        self
+    }
+
+    //Moves the x bounds outward by `fraction` of their span on both sides, e.g. 0.05 adds 5% of headroom.
+    //Call it after the bounds are set or calculated. A bound which can't move that far (e.g. an integer overflow) stays.
+    pub fn pad_x_range(mut self, fraction: f32) -> Self {
+        assert!(self.min_x_value_opt.is_some(), "There is no min_x_value!");
+        assert!(self.max_x_value_opt.is_some(), "There is no max_x_value!");
+        let (min_x_value, max_x_value) = padded(self.min_x_value_opt.as_ref().unwrap(), self.max_x_value_opt.as_ref().unwrap(), fraction);
+        self.min_x_value_opt = Some(min_x_value);
+        self.max_x_value_opt = Some(max_x_value);
+        self
+    }
+
+    //Same as `pad_x_range`, but for the y bounds
+    pub fn pad_y_range(mut self, fraction: f32) -> Self {
+        assert!(self.min_y_value_opt.is_some(), "There is no min_y_value!");
+        assert!(self.max_y_value_opt.is_some(), "There is no max_y_value!");
+        let (min_y_value, max_y_value) = padded(self.min_y_value_opt.as_ref().unwrap(), self.max_y_value_opt.as_ref().unwrap(), fraction);
+        self.min_y_value_opt = Some(min_y_value);
+        self.max_y_value_opt = Some(max_y_value);
+        self
+    }
+}
+
+impl <XV: AxisValue, XD: AxisData<XV>, YD: AxisData<bool>> ChartBuilder<XV, bool, XD, YD> {
+    //Labels of the two states of a boolean y axis (e.g. "Off" and "On").
+    //The y bounds are set to `false` and `true`, so both of them are labeled even if the data has only one.
+    pub fn y_bool_labels(mut self, false_label: String, true_label: String) -> Self {
+        self.y_label_formatter_opt = Some(Arc::new(move |yv: &bool| {
+            if *yv { true_label.clone() } else { false_label.clone() }
+        }));
+        self.min_y_value(false)
+            .max_y_value(true)
+    }
+}
+
+impl <XV: AxisValue + AxisData<XV>, YV: AxisValue + AxisData<YV>> ChartBuilder<XV, YV, XV, YV> {
+    //Adds a plot of the differences between the points of a plot and a reference (e.g. a fit) plot at the same x.
+    //The reference is interpolated between its points, points outside of it are skipped.
+    //Nothing is added if an index is out of range or the y values have no zero.
+    pub fn add_residuals(self, plot_index: usize, reference_plot_index: usize, plot_settings: PlotSettings) -> Self {
+        let residuals_opt = match (self.data.get(plot_index), self.data.get(reference_plot_index), YV::zero()) {
+            (Some((_plot_settings, edges)), Some((_reference_settings, reference_edges)), Some(zero)) => {
+                let residuals: Vec<(XV, YV)> = edges
+                    .iter()
+                    .filter_map(|(xv, yv)| {
+                        let fit = interpolate_y(reference_edges, xv)?;
+                        let residual = zero.add(fit.signed_distance_to(yv))?;
+                        Some((xv.clone(), residual))
+                    })
+                    .collect();
+                Some(residuals)
+            }
+            _ => None,
+        };
+        match residuals_opt {
+            Some(residuals) => self.add_data(plot_settings, residuals),
+            None => self,
+        }
+    }
+
+    //Adds a plot of `n` points at evenly spaced x from the first to the last x of the given plot
+    //(sorted by x), with y linearly interpolated between its points.
+    //Nothing is added if the index is out of range or the plot is empty.
+    pub fn resample_uniform(self, plot_index: usize, n: usize, plot_settings: PlotSettings) -> Self {
+        let resampled_opt = self.data.get(plot_index).and_then(|(_plot_settings, edges)| {
+            let min_x = edges.iter().map(|(xv, _yv)| xv).min_by(|xv1, xv2| xv1.compare_value(xv2))?;
+            let max_x = edges.iter().map(|(xv, _yv)| xv).max_by(|xv1, xv2| xv1.compare_value(xv2))?;
+            let distances = crate::axis::linspace(0.0, min_x.distance_to(max_x), n);
+            let last_index = distances.len().saturating_sub(1);
+            let resampled: Vec<(XV, YV)> = distances
+                .iter()
+                .enumerate()
+                .filter_map(|(i, distance)| {
+                    //The ends are taken as they are, so rounding never moves them out of the plot
+                    let xv = match i {
+                        0 => min_x.clone(),
+                        i if i == last_index => max_x.clone(),
+                        _ => min_x.add(*distance)?,
+                    };
+                    let yv = interpolate_y(edges, &xv)?;
+                    Some((xv, yv))
+                })
+                .collect();
+            Some(resampled)
+        });
+        match resampled_opt {
+            Some(resampled) => self.add_data(plot_settings, resampled),
+            None => self,
+        }
+    }
 }
 
 pub struct Chart<XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> {
@@ -164,6 +577,30 @@ pub struct Chart<XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV
     max_y_value: YV,
     total_y_distance: f32,
     data: Vec<(PlotSettings, Vec<(XD, YD)>)>,
+    x_labeled_ticks: Vec<(XV, String)>,
+    x_tick_values_opt: Option<Vec<XV>>,
+    y_tick_values_opt: Option<Vec<YV>>,
+    confidence_ellipses: Vec<ConfidenceEllipse<XV, YV>>,
+    crossing_reference_opt: Option<YV>,
+    interaction_sender_opt: Option<Sender<InteractionEvent<XV, YV>>>,
+    normal_band_opt: Option<(YV, YV, Color)>,
+    h_reference_lines: Vec<(YV, Color, String)>, //Value, color and label (empty for none)
+    x_display_transform_opt: Option<Box<dyn Fn(&XV) -> XV>>,
+    y_display_transform_opt: Option<Box<dyn Fn(&YV) -> YV>>,
+    x_label_formatter_opt: Option<Arc<dyn Fn(&XV) -> String>>,
+    y_label_formatter_opt: Option<Arc<dyn Fn(&YV) -> String>>,
+    secondary_y_range_opt: Option<(YV, YV, f32)>, //Min, max and their distance. Panning and streaming keep it.
+    tooltip_formatter_opt: Option<Arc<dyn Fn(&XD, &YD) -> String>>,
+    cursor_inside: bool, //Whether the last event of the cursor was inside of the margined area
+    generation: u64, //Number of `tick` calls so far
+    fresh_points: Vec<(usize, usize, u64)>, //Plot index, point index and generation of points still fading in
+    pan_state_opt: Option<PanState<XV, YV>>, //Set while dragging
+    animation_opt: Option<BoundsAnimation<XV, YV>>, //Set until the bounds reach the target of `set_view_state`
+    hovered_point_opt: Option<(usize, usize)>, //The last hover target reported by `update`
+    last_click_opt: Option<Instant>, //For `settings.reset_view_on_double_click`
+    detail_level: Cell<u32>,
+    detail_pending: Cell<bool>,
+    points_cache: RefCell<Option<(Size, Rc<Vec<Vec<Point>>>)>>, //Projected points for the area size, see `points`
     cache: Cache,
 }
 
@@ -187,298 +624,2187 @@ impl<XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> Chart<XV,
             max_y_value,
             total_y_distance,
             data,
+            x_labeled_ticks: Vec::new(),
+            x_tick_values_opt: None,
+            y_tick_values_opt: None,
+            confidence_ellipses: Vec::new(),
+            crossing_reference_opt: None,
+            interaction_sender_opt: None,
+            normal_band_opt: None,
+            h_reference_lines: Vec::new(),
+            x_display_transform_opt: None,
+            y_display_transform_opt: None,
+            x_label_formatter_opt: None,
+            y_label_formatter_opt: None,
+            secondary_y_range_opt: None,
+            tooltip_formatter_opt: None,
+            cursor_inside: false,
+            generation: 0,
+            fresh_points: Vec::new(),
+            pan_state_opt: None,
+            animation_opt: None,
+            hovered_point_opt: None,
+            last_click_opt: None,
+            detail_level: Cell::new(0),
+            detail_pending: Cell::new(false),
+            points_cache: RefCell::new(None),
             cache: Cache::default(),
         }
     }
 
-    fn points(&self, size: Size) -> Vec<(PlotSettings, Vec<(Point, XD, YD)>)> {
-        let width = size.width;
-        let height = size.height;
-        let result: Vec<(PlotSettings, Vec<(Point, XD, YD)>)> = self
-            .data
-            .iter()
-            .map(|(plot_settings, edges)| {
-                let result: Vec<(Point, XD, YD)> = edges
-                    .iter()
-                    .map(|(x, y)| {
-                        let x_distance = self.min_x_value.distance_to(&x.value());
-                        let x_coord = crate::math::map_inverval_value(
-                            x_distance,
-                            (0.0, self.total_x_distance),
-                            (0.0, width),
-                        );
-                        let y_distance = self.min_y_value.distance_to(&y.value());
-                        let y_coord = crate::math::map_inverval_value(
-                            y_distance,
-                            (0.0, self.total_y_distance),
-                            (0.0, height),
-                        );
-                        let point = Point::new(x_coord, height - y_coord);
-                        (point, x.to_owned(), y.to_owned())
-                    })
-                    .collect();
-                (plot_settings.clone(), result)
-            })
-            .collect();
-        result
+    //Snapshot of the visible window, which can be restored with `set_view_state`
+    pub fn view_state(&self) -> ViewState<XV, YV> {
+        ViewState {
+            min_x_value: self.min_x_value.clone(),
+            max_x_value: self.max_x_value.clone(),
+            min_y_value: self.min_y_value.clone(),
+            max_y_value: self.max_y_value.clone(),
+        }
     }
 
-    fn draw_y_label(
-        &self,
-        frame: &mut Frame,
-        padded_area: Rectangle,
-        y: f32,
-        text: &str,
-    ) {
-        let theme = self.settings.theme.clone();
-        let width = frame.width();
-        frame.stroke(
-            &Path::line(
-                Point::new(padded_area.x, y),
-                Point::new(width - padded_area.x, y),
-            ),
-            Stroke {
-                color: theme.y_label_line_color,
-                width: theme.y_label_line_width,
-                ..Default::default()
-            },
-        );
-        frame.fill_text(Text {
-            content: format!("{}", text),
-            color: theme.y_label_text_color,
-            position: Point::new(padded_area.x - 5.0, y),
-            horizontal_alignment: HorizontalAlignment::Right,
-            vertical_alignment: VerticalAlignment::Center,
-            size: theme.y_label_text_size,
-            ..Default::default()
-        });
+    //Current min and max of the x axis
+    pub fn x_bounds(&self) -> (&XV, &XV) {
+        (&self.min_x_value, &self.max_x_value)
     }
 
-    fn draw_x_label(
-        &self,
-        frame: &mut Frame,
-        padded_area: Rectangle,
-        x: f32,
-        text: &str
-    ) {
-        let theme = self.settings.theme.clone();
-        let height = frame.height();
-        frame.stroke(
-            &Path::line(
-                Point::new(x, padded_area.y),
-                Point::new(x, height - padded_area.y),
-            ),
-            Stroke {
-                color: theme.x_label_line_color,
-                width: theme.x_label_line_width,
-                ..Default::default()
-            },
+    //Current min and max of the primary y axis
+    pub fn y_bounds(&self) -> (&YV, &YV) {
+        (&self.min_y_value, &self.max_y_value)
+    }
+
+    //Fits the bounds to the current data again, like `calculate_min_max_values` (and `calculate_secondary_y_values`
+    //for a secondary axis) of the builder. Bounds without finite values to fit to stay as they are.
+    pub fn reset_view(&mut self) {
+        self.animation_opt = None;
+        self.pan_state_opt = None;
+        let x_range_opt = value_range(self.data.iter().flat_map(|(_settings, vec)| vec.iter().map(|(xv, _yv)| xv.value())));
+        let y_values = |axis| {
+            self.data
+                .iter()
+                .filter(move |(settings, _vec)| settings.axis == axis)
+                .flat_map(|(_settings, vec)| vec.iter().map(|(_xv, yv)| yv.value()))
+        };
+        let y_range_opt = value_range(y_values(YAxis::Primary));
+        let secondary_y_range_opt = value_range(y_values(YAxis::Secondary));
+        if let Some((min_y_value, max_y_value)) = secondary_y_range_opt {
+            if self.secondary_y_range_opt.is_some() {
+                let total_y_distance = min_y_value.distance_to(&max_y_value);
+                self.secondary_y_range_opt = Some((min_y_value, max_y_value, total_y_distance));
+            }
+        }
+        let (min_x_value, max_x_value) = x_range_opt.unwrap_or_else(|| (self.min_x_value.clone(), self.max_x_value.clone()));
+        let (min_y_value, max_y_value) = y_range_opt.unwrap_or_else(|| (self.min_y_value.clone(), self.max_y_value.clone()));
+        self.set_bounds(min_x_value, max_x_value, min_y_value, max_y_value);
+    }
+
+    //Changes the visible window, eased over `settings.bounds_animation` when it is set
+    pub fn set_view_state(&mut self, view_state: ViewState<XV, YV>) {
+        if self.settings.bounds_animation.is_some() {
+            self.animation_opt = Some(BoundsAnimation {
+                from: self.view_state(),
+                to: view_state,
+                started: Instant::now(),
+            });
+            return;
+        }
+        self.animation_opt = None;
+        self.set_bounds(
+            view_state.min_x_value,
+            view_state.max_x_value,
+            view_state.min_y_value,
+            view_state.max_y_value,
         );
-        frame.fill_text(Text {
-            content: format!("{}", text),
-            color: theme.x_label_text_color,
-            position: Point::new(x, height - padded_area.y + 5.0),
-            horizontal_alignment: HorizontalAlignment::Center,
-            vertical_alignment: VerticalAlignment::Top,
-            size: theme.x_label_text_size,
-            ..Default::default()
-        });
     }
-}
 
-impl <XV: data::AxisValue, YV: data::AxisValue, XD: data::AxisData<XV>, YD: data::AxisData<YV>> Program<data::Message> for Chart<XV, YV, XD, YD> {
-    fn draw(&self, bounds: Rectangle, cursor: Cursor) -> Vec<Geometry> {
-        let theme = self.settings.theme.clone();
-        
-        let size = bounds.size();
-        let width = bounds.width;
-        let height = bounds.height;
+    //Appends a point to the given plot, it fades in over `settings.fade_in_ticks` ticks.
+    //The bounds change according to `settings.streaming_bounds`. Out of range plot indices are ignored.
+    pub fn push_point(&mut self, plot_index: usize, xd: XD, yd: YD) {
+        let xv = xd.value().clone();
+        let yv = yd.value().clone();
+        if let Some((_plot_settings, edges)) = self.data.get_mut(plot_index) {
+            edges.push((xd, yd));
+            if self.settings.fade_in_ticks > 0 {
+                self.fresh_points.push((plot_index, edges.len() - 1, self.generation));
+            }
+            match self.settings.streaming_bounds {
+                StreamingBounds::Fixed => {}
+                StreamingBounds::Expand { headroom } => self.expand_bounds(&xv, &yv, headroom),
+                StreamingBounds::Slide => {
+                    self.slide_x_window(&xv);
+                    self.expand_bounds(&xv, &yv, 0.0);
+                }
+            }
+            self.invalidate();
+        }
+    }
 
-        let (ptop, pright, pbottom, pleft) = self.settings.padding.get(size);
-        let (mtop, mright, mbottom, mleft) = self.settings.margin.get(size);
+    //Moves the window of the drag by the cursor offset from its anchor, the content follows the cursor.
+    //The window keeps its size, so it can't invert.
+    fn pan_to(&mut self, cursor_position: Point, size: Size) {
+        let pan_state = match &self.pan_state_opt {
+            Some(pan_state) => pan_state,
+            None => return,
+        };
+        let margined_area = self.margined_area(size);
+        let view_state = &pan_state.view_state;
+        let dx = cursor_position.x - pan_state.anchor.x;
+        let dy = cursor_position.y - pan_state.anchor.y;
+        let dx = if self.settings.rtl { -dx } else { dx };
+        let x_shift = -crate::math::map_inverval_value(dx, (0.0, margined_area.width), (0.0, self.total_x_distance));
+        let y_shift = crate::math::map_inverval_value(dy, (0.0, margined_area.height), (0.0, self.total_y_distance));
+        let bounds_opt = (|| {
+            Some((
+                view_state.min_x_value.add(x_shift)?,
+                view_state.max_x_value.add(x_shift)?,
+                view_state.min_y_value.add(y_shift)?,
+                view_state.max_y_value.add(y_shift)?,
+            ))
+        })();
+        if let Some((min_x_value, max_x_value, min_y_value, max_y_value)) = bounds_opt {
+            self.set_bounds(min_x_value, max_x_value, min_y_value, max_y_value);
+        }
+    }
 
-        let full_area = Rectangle::new(Point::ORIGIN, size);
-        let padded_area = self.settings.padding.transform(full_area);
-        let margined_area = self.settings.margin.transform(padded_area);
+    //Grows the bounds to include the values, `headroom` is the part of the range added beyond them
+    fn expand_bounds(&mut self, xv: &XV, yv: &YV, headroom: f32) {
+        let x_outside = xv.compare_value(&self.min_x_value) == Ordering::Less
+            || xv.compare_value(&self.max_x_value) == Ordering::Greater;
+        let y_outside = yv.compare_value(&self.min_y_value) == Ordering::Less
+            || yv.compare_value(&self.max_y_value) == Ordering::Greater;
+        if !x_outside && !y_outside {
+            return;
+        }
+        let (min_x_value, max_x_value) = expanded(&self.min_x_value, &self.max_x_value, xv, headroom);
+        let (min_y_value, max_y_value) = expanded(&self.min_y_value, &self.max_y_value, yv, headroom);
+        self.set_bounds(min_x_value, max_x_value, min_y_value, max_y_value);
+    }
 
-        let cursor_position_opt = cursor.position_in(&bounds);
-        let padded_cursor_position_opt =
-            cursor_position_opt.map(|cp| Point::new(cp.x - padded_area.x, cp.y - padded_area.y));
-        let margined_cursor_position_opt = cursor_position_opt
-            .map(|cp| Point::new(cp.x - margined_area.x, cp.y - margined_area.y));
+    //Moves the x window forward so that it ends at `xv`, dropping the points which are left behind
+    fn slide_x_window(&mut self, xv: &XV) {
+        if xv.compare_value(&self.max_x_value) != Ordering::Greater {
+            return;
+        }
+        let shift = self.max_x_value.distance_to(xv);
+        let min_x_value = match self.min_x_value.add(shift) {
+            Some(min_x_value) => min_x_value,
+            None => return,
+        };
+        self.drop_points_before(&min_x_value);
+        let (min_y_value, max_y_value) = (self.min_y_value.clone(), self.max_y_value.clone());
+        self.set_bounds(min_x_value, xv.clone(), min_y_value, max_y_value);
+    }
 
-        let result = self.cache.draw(size, |frame| {
-            frame.fill(
-                &Path::rectangle(full_area.position(), full_area.size()),
-                self.settings.theme.background_color,
-            );
-            frame.fill(
-                &Path::rectangle(padded_area.position(), padded_area.size()),
-                self.settings.theme.padded_background_color,
-            );
-            self.settings.theme.margined_background_color.iter().for_each(|margined_background_color| {
-                frame.fill(
-                    &Path::rectangle(margined_area.position(), margined_area.size()),
-                    *margined_background_color,
-                );
-            });
+    //Drops the leading points of every plot with an x smaller than `xv`, e.g. to keep a sliding window of live data.
+    //The bounds stay as they are.
+    pub fn trim_older_than(&mut self, xv: &XV) {
+        if self.drop_points_before(xv) {
+            self.invalidate();
+        }
+    }
 
-            //Draw name
-            self.settings.title
-                .as_ref()
+    //Drops the leading points with an x smaller than `xv`, returns whether there were any
+    fn drop_points_before(&mut self, xv: &XV) -> bool {
+        let mut any_dropped = false;
+        for (plot_index, (_plot_settings, edges)) in self.data.iter_mut().enumerate() {
+            let dropped = edges
                 .iter()
-                .for_each(|title| {
-                    frame.fill_text(Text {
-                        content: (*title).clone(),
-                        position: Point::new(pleft, ptop / 2.0),
-                        color: self.settings.theme.title_color,
-                        size: self.settings.theme.title_size,
-                        horizontal_alignment: HorizontalAlignment::Left,
-                        vertical_alignment: VerticalAlignment::Center,
-                        ..Default::default()
-                    });
-        
-                });
-            //Draw y labels
-            let min_y_label_distance = self.settings.min_y_label_distance.get(margined_area.size());
-            let min_y_label_distance_mapped = crate::math::map_inverval_value(
-                min_y_label_distance,
-                (0.0, margined_area.height),
-                (0.0, self.total_y_distance),
-            );
-            let optimal_y_label_distance =
-                margined_area.height / (margined_area.height / min_y_label_distance).floor();
-            let optimal_y_label_distance_mapped = crate::math::map_inverval_value(
-                optimal_y_label_distance,
-                (0.0, margined_area.height),
-                (0.0, self.total_y_distance),
-            );
-            let mut yvs = self.min_y_value.get_values_in_between(
-                &self.max_y_value,
-                min_y_label_distance_mapped,
-                optimal_y_label_distance_mapped,
-            );
-            yvs.insert(0, self.min_y_value.clone());
-            yvs.push(self.max_y_value.clone());
-            let yvs = yvs;
-            for yv in yvs {
-                let text = YD::display_value(&yv);
-                let distance = self.min_y_value.distance_to(&yv);
-                let y = crate::math::map_inverval_value(
-                    distance,
-                    (0.0, self.total_y_distance),
-                    (0.0, margined_area.height),
-                );
-                self.draw_y_label(
-                    frame,
-                    padded_area,
-                    margined_area.y + margined_area.height - y,
-                    &text,
-                );
+                .take_while(|(xd, _yd)| xd.value().compare_value(xv) == Ordering::Less)
+                .count();
+            if dropped > 0 {
+                any_dropped = true;
+                edges.drain(..dropped);
+                //Fading points keep pointing at the same data
+                self.fresh_points.retain(|(fresh_plot_index, point_index, _born)| *fresh_plot_index != plot_index || *point_index >= dropped);
+                self.fresh_points
+                    .iter_mut()
+                    .filter(|(fresh_plot_index, _point_index, _born)| *fresh_plot_index == plot_index)
+                    .for_each(|(_plot_index, point_index, _born)| *point_index -= dropped);
             }
+        }
+        any_dropped
+    }
 
-            //Draw x labels
-            let min_x_label_distance = self.settings.min_x_label_distance.get(margined_area.size());
-            let min_x_label_distance_mapped = crate::math::map_inverval_value(
-                min_x_label_distance,
-                (0.0, margined_area.width),
-                (0.0, self.total_x_distance),
-            );
-            let optimal_x_label_distance =
-                margined_area.width / (margined_area.width / min_x_label_distance).floor();
-            let optimal_x_label_distance_mapped = crate::math::map_inverval_value(
-                optimal_x_label_distance,
-                (0.0, margined_area.width),
-                (0.0, self.total_x_distance),
-            );
-            let mut xvs = self.min_x_value.get_values_in_between(&self.max_x_value, min_x_label_distance_mapped, optimal_x_label_distance_mapped);
-            xvs.insert(0, self.min_x_value.clone());
-            xvs.push(self.max_x_value.clone());
-            let xvs = xvs;
-            for xv in xvs {
-                let text = XD::display_value(&xv);
-                let distance = self.min_x_value.distance_to(&xv);
-                let x = crate::math::map_inverval_value(
-                    distance,
-                    (0.0, self.total_x_distance),
-                    (0.0, margined_area.width),
-                );
-                self.draw_x_label(
-                    frame,
-                    padded_area,
-                    margined_area.x + x,
-                    &text
-                );
-            }
+    //Advances the fade in and the bounds animations by one step and lets progressive detail refine the next frame,
+    //call it regularly (e.g. from a subscription). The canvas can't ask for a redraw by itself.
+    pub fn tick(&mut self) {
+        self.generation += 1;
+        self.advance_animation();
+        if !self.fresh_points.is_empty() {
+            let generation = self.generation;
+            let fade_in_ticks = self.settings.fade_in_ticks as u64;
+            self.fresh_points.retain(|(_plot_index, _point_index, born)| generation - born < fade_in_ticks);
+            self.cache.clear();
+        }
+        if self.detail_pending.get() {
+            self.cache.clear();
+        }
+    }
+
+    //Whether the bounds are still moving towards the target of `set_view_state`
+    pub fn is_animating(&self) -> bool {
+        self.animation_opt.is_some()
+    }
+
+    //Whether progressive detail has more to draw, `tick` is needed until it is done
+    pub fn is_refining(&self) -> bool {
+        self.detail_pending.get()
+    }
+
+    //Sets the bounds for the time passed since the animation started, with an ease out cubic curve
+    fn advance_animation(&mut self) {
+        let (t, view_state) = match &self.animation_opt {
+            Some(animation) => {
+                let duration = self.settings.bounds_animation.map_or(0.0, |duration| duration.as_secs_f32());
+                let t = if duration > 0.0 { (animation.started.elapsed().as_secs_f32() / duration).min(1.0) } else { 1.0 };
+                let eased = 1.0 - (1.0 - t).powi(3);
+                let (from, to) = (&animation.from, &animation.to);
+                let view_state = ViewState {
+                    min_x_value: interpolate_value(&from.min_x_value, &to.min_x_value, eased),
+                    max_x_value: interpolate_value(&from.max_x_value, &to.max_x_value, eased),
+                    min_y_value: interpolate_value(&from.min_y_value, &to.min_y_value, eased),
+                    max_y_value: interpolate_value(&from.max_y_value, &to.max_y_value, eased),
+                };
+                (t, view_state)
+            }
+            None => return,
+        };
+        if t >= 1.0 {
+            self.animation_opt = None;
+        }
+        self.set_bounds(view_state.min_x_value, view_state.max_x_value, view_state.min_y_value, view_state.max_y_value);
+    }
+
+    //Opacity multiplier of a point, below 1.0 while it is fading in
+    fn fade_in_alpha(&self, plot_index: usize, point_index: usize) -> f32 {
+        self.fresh_points
+            .iter()
+            .find(|(fresh_plot_index, fresh_point_index, _born)| *fresh_plot_index == plot_index && *fresh_point_index == point_index)
+            .map_or(1.0, |(_plot_index, _point_index, born)| {
+                (self.generation - born + 1) as f32 / (self.settings.fade_in_ticks + 1) as f32
+            })
+    }
+
+    //Changes the visible window and redraws
+    fn set_bounds(&mut self, min_x_value: XV, max_x_value: XV, min_y_value: YV, max_y_value: YV) {
+        self.total_x_distance = min_x_value.distance_to(&max_x_value);
+        self.total_y_distance = min_y_value.distance_to(&max_y_value);
+        self.min_x_value = min_x_value;
+        self.max_x_value = max_x_value;
+        self.min_y_value = min_y_value;
+        self.max_y_value = max_y_value;
+        self.invalidate();
+    }
+
+    //Forgets everything calculated for the current data and bounds, progressive detail starts over from the coarse draw
+    fn invalidate(&mut self) {
+        self.points_cache.replace(None);
+        self.detail_level.set(0);
+        self.detail_pending.set(false);
+        self.cache.clear();
+    }
+
+    //Maps an x value to the x coordinate inside of an area with the given width
+    fn map_x(&self, xv: &XV, width: f32) -> f32 {
+        self.map_x_distance(self.min_x_value.signed_distance_to(xv), width)
+    }
+
+    //Same as `map_x`, but for a distance from the minimum x value
+    fn map_x_distance(&self, x_distance: f32, width: f32) -> f32 {
+        let x_coord = map_distance(self.settings.x_scale, &self.min_x_value, self.total_x_distance, x_distance, width);
+        //Right to left charts grow leftwards
+        if self.settings.rtl {
+            width - x_coord
+        } else {
+            x_coord
+        }
+    }
+
+    //Maps a y value to the y coordinate inside of an area with the given height (y grows downwards)
+    fn map_y(&self, yv: &YV, height: f32) -> f32 {
+        self.map_y_distance(self.min_y_value.signed_distance_to(yv), height)
+    }
+
+    //Same as `map_y`, but for a distance from the minimum y value
+    fn map_y_distance(&self, y_distance: f32, height: f32) -> f32 {
+        let y_coord = map_distance(self.settings.y_scale, &self.min_y_value, self.total_y_distance, y_distance, height);
+        height - y_coord
+    }
+
+    //Minimum, maximum and total distance of the y axis, the secondary one falls back to the primary one
+    fn y_range(&self, axis: YAxis) -> (&YV, &YV, f32) {
+        match (axis, &self.secondary_y_range_opt) {
+            (YAxis::Secondary, Some((min_y_value, max_y_value, total_y_distance))) => (min_y_value, max_y_value, *total_y_distance),
+            _ => (&self.min_y_value, &self.max_y_value, self.total_y_distance),
+        }
+    }
+
+    //Same as `map_y`, but for the given y axis
+    fn map_axis_y(&self, axis: YAxis, yv: &YV, height: f32) -> f32 {
+        let (min_y_value, _max_y_value, total_y_distance) = self.y_range(axis);
+        let y_coord = map_distance(self.settings.y_scale, min_y_value, total_y_distance, min_y_value.signed_distance_to(yv), height);
+        height - y_coord
+    }
+
+    //Inverse of `map_x`, `None` if the value type can't represent the result
+    fn unmap_x(&self, x: f32, width: f32) -> Option<XV> {
+        let x_coord = if self.settings.rtl { width - x } else { x };
+        let x_distance = unmap_distance(self.settings.x_scale, &self.min_x_value, self.total_x_distance, x_coord, width);
+        self.min_x_value.add(x_distance)
+    }
+
+    //Inverse of `map_y`, `None` if the value type can't represent the result
+    fn unmap_y(&self, y: f32, height: f32) -> Option<YV> {
+        let y_distance = unmap_distance(self.settings.y_scale, &self.min_y_value, self.total_y_distance, height - y, height);
+        self.min_y_value.add(y_distance)
+    }
+
+    //Points of the y = x line inside of an area with the given size, sampled so it follows non-linear scales.
+    //Empty if a value type has no zero or the x and y ranges don't overlap.
+    fn identity_points(&self, size: Size) -> Vec<Point> {
+        const SEGMENTS: usize = 64;
+        let (x_zero, y_zero) = match (XV::zero(), YV::zero()) {
+            (Some(x_zero), Some(y_zero)) => (x_zero, y_zero),
+            _ => return Vec::new(),
+        };
+        let from = x_zero.signed_distance_to(&self.min_x_value).max(y_zero.signed_distance_to(&self.min_y_value));
+        let to = x_zero.signed_distance_to(&self.max_x_value).min(y_zero.signed_distance_to(&self.max_y_value));
+        if from > to {
+            return Vec::new();
+        }
+        crate::axis::linspace(from, to, SEGMENTS + 1)
+            .into_iter()
+            .map(|value| {
+                let x_distance = self.min_x_value.signed_distance_to(&x_zero) + value;
+                let y_distance = self.min_y_value.signed_distance_to(&y_zero) + value;
+                Point::new(self.map_x_distance(x_distance, size.width), self.map_y_distance(y_distance, size.height))
+            })
+            .collect()
+    }
+
+    //Outline of the ellipse as a polygon inside of an area with the given size
+    fn ellipse_points(&self, ellipse: &ConfidenceEllipse<XV, YV>, size: Size) -> Vec<Point> {
+        const SEGMENTS: usize = 64;
+        let (reference_x, reference_y) = &ellipse.reference;
+        let reference_x_distance = self.min_x_value.signed_distance_to(reference_x);
+        let reference_y_distance = self.min_y_value.signed_distance_to(reference_y);
+        let (center_x, center_y) = ellipse.center;
+        let (a, b) = ellipse.semi_axes;
+        let (sin_angle, cos_angle) = ellipse.angle.sin_cos();
+        (0..=SEGMENTS)
+            .map(|i| {
+                let theta = i as f32 / SEGMENTS as f32 * 2.0 * std::f32::consts::PI;
+                let (sin_theta, cos_theta) = theta.sin_cos();
+                let dx = center_x + a * cos_theta * cos_angle - b * sin_theta * sin_angle;
+                let dy = center_y + a * cos_theta * sin_angle + b * sin_theta * cos_angle;
+                Point::new(
+                    self.map_x_distance(reference_x_distance + dx, size.width),
+                    self.map_y_distance(reference_y_distance + dy, size.height),
+                )
+            })
+            .collect()
+    }
+
+    //Projected points of every plot, aligned with `self.data`.
+    //Cached until the size, the bounds or the data change, so redrawing on every cursor move stays cheap.
+    fn points(&self, size: Size) -> Rc<Vec<Vec<Point>>> {
+        if let Some((cached_size, points)) = &*self.points_cache.borrow() {
+            if *cached_size == size {
+                return Rc::clone(points);
+            }
+        }
+        let points = Rc::new(self.project_points(size));
+        self.points_cache.replace(Some((size, Rc::clone(&points))));
+        points
+    }
+
+    fn project_points(&self, size: Size) -> Vec<Vec<Point>> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(plot_index, (plot_settings, edges))| {
+                edges
+                    .iter()
+                    .enumerate()
+                    .map(|(point_index, (x, y))| {
+                        if !x.value().is_finite() || !y.value().is_finite() {
+                            return Point::new(f32::NAN, f32::NAN);
+                        }
+                        //Seeded by the indices, so the points stay in place between frames
+                        let jitter = if plot_settings.jitter != 0.0 {
+                            let seed = ((plot_index as u64) << 32) ^ point_index as u64;
+                            plot_settings.jitter * crate::math::seeded_unit(seed)
+                        } else {
+                            0.0
+                        };
+                        Point::new(self.map_x(x.value(), size.width) + jitter, self.map_axis_y(plot_settings.axis, y.value(), size.height))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    //Positions of the points of every plot (with its index) on a canvas of `size`, as they are drawn.
+    //Useful for overlays aligned with the data.
+    pub fn screen_points(&self, size: Size) -> Vec<(usize, Vec<Point>)> {
+        let margined_area = self.margined_area(size);
+        let offset = Vector::new(margined_area.x, margined_area.y);
+        self.points(margined_area.size())
+            .iter()
+            .map(|vec| vec.iter().map(|point| *point + offset).collect())
+            .enumerate()
+            .collect()
+    }
+
+    //The smallest rectangle (relative to the canvas of `size`) enclosing every drawn point, `None` without points
+    pub fn data_pixel_bounds(&self, size: Size) -> Option<Rectangle> {
+        let screen_points = self.screen_points(size);
+        let mut all_points = screen_points
+            .iter()
+            .flat_map(|(_plot_index, vec)| vec.iter())
+            .filter(|point| !is_gap(point));
+        let first = *all_points.next()?;
+        let (min, max) = all_points.fold((first, first), |(min, max), point| {
+            (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            )
+        });
+        Some(Rectangle::new(min, Size::new(max.x - min.x, max.y - min.y)))
+    }
+
+    //Finds the point closest to the cursor, as (plot index, point index).
+    //Every plot only counts points within its own `point_selection_distance`, unless `settings.point_selection` is `NearestX`:
+    //then the cursor only has to be inside of the margined area of `size`.
+    //Points outside of the margined area aren't drawn, so they can't be selected either.
+    fn find_selected_point(&self, points: &[Vec<Point>], cursor_position: Point, size: Size) -> Option<(usize, usize)> {
+        let area = Rectangle::new(Point::ORIGIN, size);
+        if self.settings.point_selection == PointSelection::NearestX {
+            if !area.contains(cursor_position) {
+                return None;
+            }
+            return self
+                .nearest_x_points(points, cursor_position.x)
+                .into_iter()
+                .filter(|(plot_index, point_index)| area.contains(points[*plot_index][*point_index]))
+                .min_by(|(plot_index1, point_index1), (plot_index2, point_index2)| {
+                    let distance1 = cursor_position.distance(points[*plot_index1][*point_index1]);
+                    let distance2 = cursor_position.distance(points[*plot_index2][*point_index2]);
+                    distance1.total_cmp(&distance2)
+                });
+        }
+        let mut candidates = points
+            .iter()
+            .zip(self.data.iter())
+            .enumerate()
+            .filter_map(|(plot_index, (vec, (plot_settings, _edges)))| {
+                vec.iter()
+                    .enumerate()
+                    .filter(|(_point_index, point)| area.contains(**point))
+                    .map(|(point_index, point)| (point_index, cursor_position.distance(*point)))
+                    .filter(|(_point_index, distance)| *distance <= plot_settings.point_selection_distance)
+                    .min_by(|(_index1, f1), (_index2, f2)| f1.total_cmp(f2))
+                    .map(|(point_index, distance)| (plot_index, point_index, distance))
+            });
+        let selected_opt = match self.settings.coincident_points {
+            CoincidentPolicy::FirstSeries => candidates.next(),
+            //Candidates come by plot index, so a later one has to be clearly closer to win
+            CoincidentPolicy::ClosestStable | CoincidentPolicy::ShowAll => candidates.fold(None, |best_opt, candidate| {
+                match best_opt {
+                    Some((_plot_index, _point_index, best_distance)) if candidate.2 >= best_distance - COINCIDENCE_DISTANCE => best_opt,
+                    _ => Some(candidate),
+                }
+            }),
+        };
+        selected_opt.map(|(plot_index, point_index, _distance)| (plot_index, point_index))
+    }
+
+    //The point of every plot closest to the x coordinate, as (plot index, point index)
+    fn nearest_x_points(&self, points: &[Vec<Point>], x: f32) -> Vec<(usize, usize)> {
+        points
+            .iter()
+            .enumerate()
+            .filter_map(|(plot_index, vec)| {
+                vec.iter()
+                    .enumerate()
+                    .filter(|(_point_index, point)| !is_gap(point))
+                    .min_by(|(_index1, p1), (_index2, p2)| (p1.x - x).abs().total_cmp(&(p2.x - x).abs()))
+                    .map(|(point_index, _point)| (plot_index, point_index))
+            })
+            .collect()
+    }
+
+    //The selected point followed by the points of the other plots drawn at about the same place
+    fn coincident_points(&self, points: &[Vec<Point>], selected: (usize, usize)) -> Vec<(usize, usize)> {
+        let (selected_plot_index, selected_point_index) = selected;
+        let selected_point = points[selected_plot_index][selected_point_index];
+        let mut result = vec![selected];
+        for (plot_index, vec) in points.iter().enumerate() {
+            if plot_index == selected_plot_index {
+                continue;
+            }
+            let coincident_opt = vec
+                .iter()
+                .position(|point| point.distance(selected_point) <= COINCIDENCE_DISTANCE);
+            if let Some(point_index) = coincident_opt {
+                result.push((plot_index, point_index));
+            }
+        }
+        result
+    }
+
+    //Anchors of the title and the subtitle, and their alignment.
+    //They are centered together in the top padding, the title on top.
+    fn title_positions(&self, size: Size) -> (Point, Point, HorizontalAlignment) {
+        let theme = &self.settings.theme;
+        let (anchor, horizontal_alignment) = self.title_position(size);
+        let title_size = if self.settings.title.is_some() { theme.title_size } else { 0.0 };
+        let subtitle_size = if self.settings.subtitle.is_some() { theme.subtitle_size } else { 0.0 };
+        let top = anchor.y - (title_size + subtitle_size) / 2.0;
+        (
+            Point::new(anchor.x, top + title_size / 2.0),
+            Point::new(anchor.x, top + title_size + subtitle_size / 2.0),
+            horizontal_alignment,
+        )
+    }
+
+    //Anchor and alignment of the title block, in the middle of the top padding
+    fn title_position(&self, size: Size) -> (Point, HorizontalAlignment) {
+        let (ptop, pright, _pbottom, pleft) = self.settings.padding.get(size);
+        let horizontal_alignment = if self.settings.rtl {
+            mirror_alignment(self.settings.title_alignment)
+        } else {
+            self.settings.title_alignment
+        };
+        let x = match horizontal_alignment {
+            HorizontalAlignment::Left => pleft,
+            HorizontalAlignment::Center => pleft + (size.width - pleft - pright) / 2.0,
+            HorizontalAlignment::Right => size.width - pright,
+        };
+        (Point::new(x, ptop / 2.0), horizontal_alignment)
+    }
+
+    //The area inside of the padding and the margin, where the data is drawn
+    fn margined_area(&self, size: Size) -> Rectangle {
+        let full_area = Rectangle::new(Point::ORIGIN, size);
+        let padded_area = self.settings.padding.transform(full_area);
+        self.settings.margin.transform(padded_area)
+    }
+
+    //Finds the point of the given plot closest to `cursor_position` (relative to the canvas of `size`),
+    //ignoring all the other plots. Returns the point index and its values.
+    pub fn nearest_point_in_plot(&self, plot_index: usize, cursor_position: Point, size: Size) -> Option<(usize, XV, YV)> {
+        let (plot_settings, edges) = self.data.get(plot_index)?;
+        let margined_area = self.margined_area(size);
+        let margined_cursor_position = Point::new(cursor_position.x - margined_area.x, cursor_position.y - margined_area.y);
+        edges
+            .iter()
+            .enumerate()
+            .map(|(point_index, (xd, yd))| {
+                let point = Point::new(
+                    self.map_x(xd.value(), margined_area.width),
+                    self.map_axis_y(plot_settings.axis, yd.value(), margined_area.height),
+                );
+                (point_index, margined_cursor_position.distance(point))
+            })
+            .filter(|(_point_index, distance)| *distance <= plot_settings.point_selection_distance)
+            .min_by(|(_index1, f1), (_index2, f2)| f1.total_cmp(f2))
+            .map(|(point_index, _distance)| {
+                let (xd, yd) = &edges[point_index];
+                (point_index, xd.value().clone(), yd.value().clone())
+            })
+    }
+
+    //Finds the plot whose line is closest to the cursor.
+    //Every plot only counts lines within its own `line_selection_distance`.
+    fn find_selected_plot(&self, points: &[Vec<Point>], cursor_position: Point) -> Option<usize> {
+        points
+            .iter()
+            .zip(self.data.iter())
+            .enumerate()
+            .filter_map(|(plot_index, (vec, (plot_settings, _edges)))| {
+                vec.windows(2)
+                    .filter(|slice| !is_gap(&slice[0]) && !is_gap(&slice[1]))
+                    .map(|slice| crate::math::point_to_interval_distance(cursor_position, slice[0], slice[1]))
+                    .filter(|distance| *distance <= plot_settings.line_selection_distance)
+                    .min_by(|f1, f2| f1.total_cmp(f2))
+                    .map(|distance| (plot_index, distance))
+            })
+            .min_by(|(_plot1, distance1), (_plot2, distance2)| distance1.total_cmp(distance2))
+            .map(|(plot_index, _distance)| plot_index)
+    }
+
+    //Reads the y value of every plot at `x` according to `settings.readout_mode`.
+    //The result is aligned with the plots, `None` means there is no data at `x`.
+    pub fn readout(&self, x: &XV) -> Vec<Option<YV>> {
+        self.data
+            .iter()
+            .map(|(_plot_settings, edges)| {
+                let min_x = edges.iter().map(|(xd, _yd)| xd.value()).min_by(|xv1, xv2| xv1.compare_value(xv2))?;
+                let max_x = edges.iter().map(|(xd, _yd)| xd.value()).max_by(|xv1, xv2| xv1.compare_value(xv2))?;
+                if x.compare_value(min_x) == Ordering::Less || x.compare_value(max_x) == Ordering::Greater {
+                    return None;
+                }
+                match self.settings.readout_mode {
+                    ReadoutMode::Nearest => edges
+                        .iter()
+                        .min_by(|(xd1, _yd1), (xd2, _yd2)| {
+                            let distance1 = x.signed_distance_to(xd1.value()).abs();
+                            let distance2 = x.signed_distance_to(xd2.value()).abs();
+                            distance1.total_cmp(&distance2)
+                        })
+                        .map(|(_xd, yd)| yd.value().clone()),
+                    ReadoutMode::Interpolate => interpolate_y(edges, x),
+                    ReadoutMode::None => None,
+                }
+            })
+            .collect()
+    }
+
+    //Point count, extremes and mean of the y values of `settings.stats_plot_index` plot
+    fn stats_text(&self) -> Option<String> {
+        let (_plot_settings, edges) = self.data.get(self.settings.stats_plot_index)?;
+        let count = edges.len();
+        let min_opt = edges
+            .iter()
+            .map(|(_xd, yd)| yd.value())
+            .min_by(|yv1, yv2| yv1.compare_value(yv2));
+        let max_opt = edges
+            .iter()
+            .map(|(_xd, yd)| yd.value())
+            .max_by(|yv1, yv2| yv1.compare_value(yv2));
+        let mut text = format!("n: {}", count);
+        if let (Some(min), Some(max)) = (min_opt, max_opt) {
+            //The mean is calculated on distances from the minimum, so it works for every AxisValue
+            let mean_distance = edges
+                .iter()
+                .map(|(_xd, yd)| min.distance_to(yd.value()))
+                .sum::<f32>() / count as f32;
+            text.push_str(&format!("  min: {}", self.display_y(min)));
+            text.push_str(&format!("  max: {}", self.display_y(max)));
+            if let Some(mean) = min.add(mean_distance) {
+                text.push_str(&format!("  mean: {}", self.display_y(&mean)));
+            }
+        }
+        Some(text)
+    }
+
+    //Positions (relative to the canvas) and texts of the y labels
+    fn y_labels(&self, margined_area: Rectangle) -> Vec<(f32, String)> {
+        self.axis_y_labels(YAxis::Primary, margined_area)
+    }
+
+    //Same as `y_labels`, but for the given y axis
+    fn axis_y_labels(&self, axis: YAxis, margined_area: Rectangle) -> Vec<(f32, String)> {
+        let (min_y_value, max_y_value, total_y_distance) = self.y_range(axis);
+        let min_y_label_distance = label_distance(self.settings.y_label_count, self.settings.min_y_label_distance, margined_area.height, margined_area.size());
+        let min_y_label_distance_mapped = crate::math::map_inverval_value(
+            min_y_label_distance,
+            (0.0, margined_area.height),
+            (0.0, total_y_distance),
+        );
+        let optimal_y_label_distance =
+            margined_area.height / (margined_area.height / min_y_label_distance).floor();
+        let optimal_y_label_distance_mapped = crate::math::map_inverval_value(
+            optimal_y_label_distance,
+            (0.0, margined_area.height),
+            (0.0, total_y_distance),
+        );
+        let yvs = match (axis, &self.y_tick_values_opt) {
+            (YAxis::Primary, Some(y_tick_values)) => within(y_tick_values, min_y_value, max_y_value),
+            _ => {
+                let mut yvs = match self.settings.y_scale {
+                    ScaleKind::Linear => min_y_value.get_values_in_between(
+                        max_y_value,
+                        min_y_label_distance_mapped,
+                        snap_step(YV::preferred_steps(), optimal_y_label_distance_mapped),
+                    ),
+                    scale => scale_tick_values(
+                        scale,
+                        min_y_value,
+                        total_y_distance,
+                        margined_area.height,
+                        min_y_label_distance,
+                    ),
+                };
+                yvs.insert(0, min_y_value.clone());
+                yvs.push(max_y_value.clone());
+                yvs
+            }
+        };
+        yvs.iter()
+            .map(|yv| {
+                let text = self.format_number(self.display_y(yv));
+                (margined_area.y + self.map_axis_y(axis, yv, margined_area.height), text)
+            })
+            .collect()
+    }
+
+    //Text of an x value, after the display transform
+    fn display_x(&self, xv: &XV) -> String {
+        let transformed_opt = self.x_display_transform_opt.as_ref().map(|transform| transform(xv));
+        let xv = transformed_opt.as_ref().unwrap_or(xv);
+        match &self.x_label_formatter_opt {
+            Some(formatter) => formatter(xv),
+            None => XD::display_value(xv),
+        }
+    }
+
+    //Text of a y value, after the display transform
+    fn display_y(&self, yv: &YV) -> String {
+        let transformed_opt = self.y_display_transform_opt.as_ref().map(|transform| transform(yv));
+        let yv = transformed_opt.as_ref().unwrap_or(yv);
+        match &self.y_label_formatter_opt {
+            Some(formatter) => formatter(yv),
+            None => YD::display_value(yv),
+        }
+    }
+
+    //Description of an x data, the transform and the formatter replace the custom description.
+    //Numeric descriptions are formatted like the labels.
+    fn describe_x(&self, xd: &XD) -> String {
+        let description = if self.x_display_transform_opt.is_some() || self.x_label_formatter_opt.is_some() {
+            self.display_x(xd.value())
+        } else {
+            xd.description()
+        };
+        self.format_number(description)
+    }
+
+    //Description of a y data, the transform and the formatter replace the custom description.
+    //Numeric descriptions are formatted like the labels.
+    fn describe_y(&self, yd: &YD) -> String {
+        let description = if self.y_display_transform_opt.is_some() || self.y_label_formatter_opt.is_some() {
+            self.display_y(yd.value())
+        } else {
+            yd.description()
+        };
+        self.format_number(description)
+    }
+
+    //Applies the number formatting settings to a label text
+    fn format_number(&self, text: String) -> String {
+        if let NumberFormat::Scientific { threshold, precision } = self.settings.number_format {
+            if let Some(scientific) = format::scientific(&text, threshold, precision) {
+                return scientific;
+            }
+        }
+        match self.settings.thousands_separator {
+            Some(separator) => format::group_thousands(&text, separator),
+            None => text,
+        }
+    }
+
+    //Positions (relative to the canvas) and texts of the x labels, including the custom labeled ticks
+    fn x_labels(&self, margined_area: Rectangle) -> Vec<(f32, String)> {
+        let min_x_label_distance = label_distance(self.settings.x_label_count, self.settings.min_x_label_distance, margined_area.width, margined_area.size());
+        let min_x_label_distance_mapped = crate::math::map_inverval_value(
+            min_x_label_distance,
+            (0.0, margined_area.width),
+            (0.0, self.total_x_distance),
+        );
+        let optimal_x_label_distance =
+            margined_area.width / (margined_area.width / min_x_label_distance).floor();
+        let optimal_x_label_distance_mapped = crate::math::map_inverval_value(
+            optimal_x_label_distance,
+            (0.0, margined_area.width),
+            (0.0, self.total_x_distance),
+        );
+        let xvs = match &self.x_tick_values_opt {
+            Some(x_tick_values) => within(x_tick_values, &self.min_x_value, &self.max_x_value),
+            None => {
+                let mut xvs = match self.settings.x_scale {
+                    ScaleKind::Linear => self.min_x_value.get_values_in_between(
+                        &self.max_x_value,
+                        min_x_label_distance_mapped,
+                        snap_step(XV::preferred_steps(), optimal_x_label_distance_mapped),
+                    ),
+                    scale => scale_tick_values(
+                        scale,
+                        &self.min_x_value,
+                        self.total_x_distance,
+                        margined_area.width,
+                        min_x_label_distance,
+                    ),
+                };
+                xvs.insert(0, self.min_x_value.clone());
+                xvs.push(self.max_x_value.clone());
+                xvs
+            }
+        };
+        let labels = xvs.into_iter().map(|xv| {
+            let text = self.format_number(self.display_x(&xv));
+            (xv, text)
+        });
+        let labeled_ticks = self.x_labeled_ticks
+            .iter()
+            .filter(|(xv, _text)| {
+                xv.compare_value(&self.min_x_value) != Ordering::Less
+                    && xv.compare_value(&self.max_x_value) != Ordering::Greater
+            })
+            .cloned();
+        labels
+            .chain(labeled_ticks)
+            .map(|(xv, text)| (margined_area.x + self.map_x(&xv, margined_area.width), text))
+            .collect()
+    }
+
+    //The y and the x of the labeled gridlines nearest to the cursor, when they get highlighted and the cursor is inside
+    fn highlighted_gridlines(
+        &self,
+        y_labels: &[(f32, String)],
+        x_labels: &[(f32, String)],
+        margined_area: Rectangle,
+        cursor_position_opt: Option<Point>,
+    ) -> (Option<f32>, Option<f32>) {
+        let inside_cursor_position_opt = cursor_position_opt.filter(|cp| margined_area.contains(*cp));
+        match inside_cursor_position_opt {
+            Some(cursor_position) if self.settings.highlight_nearest_gridline => {
+                let nearest = |labels: &[(f32, String)], target: f32| {
+                    labels
+                        .iter()
+                        .map(|(coord, _text)| *coord)
+                        .min_by(|c1, c2| (c1 - target).abs().total_cmp(&(c2 - target).abs()))
+                };
+                (nearest(y_labels, cursor_position.y), nearest(x_labels, cursor_position.x))
+            }
+            _ => (None, None),
+        }
+    }
+
+    //Minor gridlines, labels with their gridlines and tick marks, the highlighted gridlines and the x axis at zero
+    fn draw_grid(&self, frame: &mut Frame, padded_area: Rectangle, margined_area: Rectangle, cursor_position_opt: Option<Point>) {
+        let theme = &self.settings.theme;
+        let y_labels = self.y_labels(margined_area);
+        let x_labels = self.x_labels(margined_area);
+
+        //Draw minor gridlines, thinner and lighter than the labeled ones
+        if let Some(minor_grid_density) = self.settings.minor_grid_density {
+            let spacing = minor_grid_density.get(margined_area.size());
+            let minor_stroke = |color: Color, width: f32| Stroke {
+                color: Color {
+                    a: color.a * 0.5,
+                    ..color
+                },
+                width: width / 2.0,
+                ..Default::default()
+            };
+            let mut y_coords: Vec<f32> = y_labels.iter().map(|(y, _text)| *y).collect();
+            y_coords.sort_by(|y1, y2| y1.total_cmp(y2));
+            for y in crate::math::minor_coords(&y_coords, spacing) {
+                frame.stroke(
+                    &Path::line(Point::new(margined_area.x, y), Point::new(margined_area.x + margined_area.width, y)),
+                    minor_stroke(theme.y_label_line_color, theme.y_label_line_width),
+                );
+            }
+            let mut x_coords: Vec<f32> = x_labels.iter().map(|(x, _text)| *x).collect();
+            x_coords.sort_by(|x1, x2| x1.total_cmp(x2));
+            for x in crate::math::minor_coords(&x_coords, spacing) {
+                frame.stroke(
+                    &Path::line(Point::new(x, margined_area.y), Point::new(x, margined_area.y + margined_area.height)),
+                    minor_stroke(theme.x_label_line_color, theme.x_label_line_width),
+                );
+            }
+        }
+
+        //Draw y labels
+        let size = frame.size();
+        for (y, text) in y_labels.iter() {
+            let geometry = self.y_label_geometry(size, padded_area, margined_area, YAxis::Primary, *y, text);
+            self.draw_label(frame, geometry, theme.y_label_line_color, theme.y_label_line_width);
+        }
+        if self.secondary_y_range_opt.is_some() {
+            for (y, text) in self.axis_y_labels(YAxis::Secondary, margined_area).iter() {
+                let geometry = self.y_label_geometry(size, padded_area, margined_area, YAxis::Secondary, *y, text);
+                self.draw_label(frame, geometry, theme.y_label_line_color, theme.y_label_line_width);
+            }
+        }
+
+        //Draw x labels
+        for (x, text) in x_labels.iter() {
+            let geometry = self.x_label_geometry(size, padded_area, margined_area, *x, text);
+            self.draw_label(frame, geometry, theme.x_label_line_color, theme.x_label_line_width);
+        }
+
+        //Draw highlighted gridlines over the regular ones
+        let (highlighted_y_opt, highlighted_x_opt) = self.highlighted_gridlines(&y_labels, &x_labels, margined_area, cursor_position_opt);
+        if let Some(y) = highlighted_y_opt {
+            frame.stroke(
+                &Path::line(Point::new(margined_area.x, y), Point::new(margined_area.x + margined_area.width, y)),
+                Stroke {
+                    color: theme.grid_highlight_color,
+                    width: theme.y_label_line_width * 2.0,
+                    ..Default::default()
+                },
+            );
+        }
+        if let Some(x) = highlighted_x_opt {
+            frame.stroke(
+                &Path::line(Point::new(x, margined_area.y), Point::new(x, margined_area.y + margined_area.height)),
+                Stroke {
+                    color: theme.grid_highlight_color,
+                    width: theme.x_label_line_width * 2.0,
+                    ..Default::default()
+                },
+            );
+        }
+
+        //Draw the x axis at zero
+        if let Some(axis_y) = self.x_axis_y(margined_area) {
+            frame.stroke(
+                &Path::line(Point::new(margined_area.x, axis_y), Point::new(margined_area.x + margined_area.width, axis_y)),
+                Stroke {
+                    color: theme.x_label_line_color,
+                    width: theme.x_label_line_width,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    //The x axis title centered at the bottom edge, the y axis title rotated and centered at the edge next to the y labels
+    fn draw_axis_titles(&self, frame: &mut Frame, margined_area: Rectangle) {
+        let theme = &self.settings.theme;
+        let size = theme.axis_title_size;
+        if let Some(x_axis_title) = &self.settings.x_axis_title {
+            frame.fill_text(Text {
+                content: x_axis_title.clone(),
+                position: Point::new(margined_area.x + margined_area.width / 2.0, frame.height() - 5.0),
+                color: theme.x_label_text_color,
+                size,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Bottom,
+                ..Default::default()
+            });
+        }
+        if let Some(y_axis_title) = &self.settings.y_axis_title {
+            //Reading bottom to top on the left, top to bottom on the right of right to left charts
+            let (x, rotation) = if self.settings.rtl {
+                (frame.width() - 5.0 - size / 2.0, 90.0_f32)
+            } else {
+                (5.0 + size / 2.0, -90.0_f32)
+            };
+            let anchor = Point::new(x, margined_area.y + margined_area.height / 2.0);
+            frame.with_save(|frame| {
+                frame.translate(Vector::new(anchor.x, anchor.y));
+                frame.rotate(rotation.to_radians());
+                frame.fill_text(Text {
+                    content: y_axis_title.clone(),
+                    position: Point::ORIGIN,
+                    color: theme.y_label_text_color,
+                    size,
+                    horizontal_alignment: HorizontalAlignment::Center,
+                    vertical_alignment: VerticalAlignment::Center,
+                    ..Default::default()
+                });
+            });
+        }
+    }
+
+    //Lines through the cursor across the margined area, and the values under the cursor next to the axes
+    fn draw_crosshair(&self, frame: &mut Frame, margined_area: Rectangle, cursor_position: Point, crosshair: CrosshairSettings) {
+        let theme = &self.settings.theme;
+        let stroke = Stroke {
+            color: crosshair.color,
+            width: crosshair.width,
+            ..Default::default()
+        };
+        let lines = [
+            [Point::new(cursor_position.x, margined_area.y), Point::new(cursor_position.x, margined_area.y + margined_area.height)],
+            [Point::new(margined_area.x, cursor_position.y), Point::new(margined_area.x + margined_area.width, cursor_position.y)],
+        ];
+        for line in lines.iter() {
+            if crosshair.dashed {
+                for dash_points in crate::math::dash_polyline(line, 6.0, 4.0) {
+                    frame.stroke(&Path::line(dash_points[0], dash_points[dash_points.len() - 1]), stroke);
+                }
+            } else {
+                frame.stroke(&Path::line(line[0], line[1]), stroke);
+            }
+        }
+        //The y text is the readout of the plots at the cursor x, not the y under the cursor
+        let x_value_opt = self.unmap_x(cursor_position.x - margined_area.x, margined_area.width);
+        let y_content = match &x_value_opt {
+            Some(xv) => self.crosshair_y_text(xv),
+            None => self.readout_text(None),
+        };
+        if let Some(xv) = x_value_opt {
+            frame.fill_text(Text {
+                content: self.display_x(&xv),
+                position: Point::new(cursor_position.x, margined_area.y + margined_area.height + 2.0),
+                color: crosshair.color,
+                size: theme.x_label_text_size,
+                horizontal_alignment: HorizontalAlignment::Center,
+                vertical_alignment: VerticalAlignment::Top,
+                ..Default::default()
+            });
+        }
+        //Next to the y axis, which is on the right in right to left charts
+        let (x, horizontal_alignment) = if self.settings.rtl {
+            (margined_area.x + margined_area.width + 2.0, HorizontalAlignment::Left)
+        } else {
+            (margined_area.x - 2.0, HorizontalAlignment::Right)
+        };
+        frame.fill_text(Text {
+            content: y_content,
+            position: Point::new(x, cursor_position.y),
+            color: crosshair.color,
+            size: theme.y_label_text_size,
+            horizontal_alignment,
+            vertical_alignment: VerticalAlignment::Center,
+            ..Default::default()
+        });
+    }
+
+    //Descriptions of the selected point (and the points at the same place, if they are shown too)
+    fn tooltip_content(&self, points: &[Vec<Point>], selected_point: (usize, usize)) -> String {
+        let theme = &self.settings.theme;
+        if self.settings.point_selection == PointSelection::NearestX {
+            let content = self.nearest_x_content(points, selected_point);
+            return match theme.tooltip_max_width {
+                Some(tooltip_max_width) => text::wrap(&content, theme.data_description_size, tooltip_max_width),
+                None => content,
+            };
+        }
+        let described_points = if self.settings.coincident_points == CoincidentPolicy::ShowAll {
+            self.coincident_points(points, selected_point)
+        } else {
+            vec![selected_point]
+        };
+        let mut content = String::new();
+        for (plot_index, point_index) in described_points {
+            let (xd, yd) = &self.data[plot_index].1[point_index];
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            match &self.tooltip_formatter_opt {
+                Some(formatter) => content.push_str(&formatter(xd, yd)),
+                None => {
+                    content.push_str(&self.describe_x(xd));
+                    content.push('\n');
+                    content.push_str(&self.describe_y(yd));
+                }
+            }
+        }
+        if let Some(tooltip_max_width) = theme.tooltip_max_width {
+            content = text::wrap(&content, theme.data_description_size, tooltip_max_width);
+        }
+        content
+    }
+
+    //The x of the selected point, then a line with the readout of every plot at that x (prefixed by the plot name).
+    //The tooltip formatter gives one line per plot instead, with the point of the plot closest to that x.
+    fn nearest_x_content(&self, points: &[Vec<Point>], selected_point: (usize, usize)) -> String {
+        let (selected_plot_index, selected_point_index) = selected_point;
+        let (selected_xd, _yd) = &self.data[selected_plot_index].1[selected_point_index];
+        if let Some(formatter) = &self.tooltip_formatter_opt {
+            let nearest_points = self.nearest_x_points(points, points[selected_plot_index][selected_point_index].x);
+            let lines: Vec<String> = nearest_points
+                .into_iter()
+                .map(|(plot_index, point_index)| {
+                    let (xd, yd) = &self.data[plot_index].1[point_index];
+                    formatter(xd, yd)
+                })
+                .collect();
+            return lines.join("\n");
+        }
+        let mut lines = vec![self.describe_x(selected_xd)];
+        for ((plot_settings, _edges), yv_opt) in self.data.iter().zip(self.readout(selected_xd.value())) {
+            let value = self.readout_text(yv_opt.as_ref());
+            lines.push(match &plot_settings.name {
+                Some(name) => format!("{}: {}", name, value),
+                None => value,
+            });
+        }
+        lines.join("\n")
+    }
+
+    //A value read by `readout`, or "no data"
+    fn readout_text(&self, yv_opt: Option<&YV>) -> String {
+        match yv_opt {
+            Some(yv) => self.display_y(yv),
+            None => "no data".to_owned(),
+        }
+    }
+
+    //The y text of the crosshair: the readout of every plot which has data at `x`, or "no data" if none of them does
+    fn crosshair_y_text(&self, x: &XV) -> String {
+        let values: Vec<String> = self.readout(x)
+            .iter()
+            .flatten()
+            .map(|yv| self.display_y(yv))
+            .collect();
+        if values.is_empty() {
+            self.readout_text(None)
+        } else {
+            values.join(" / ")
+        }
+    }
+
+    //Draws the tooltip box diagonally `clearance` away from `point` (relative to the canvas),
+    //flipped to the other side where it wouldn't fit into the margined area, and a leader line to it
+    fn draw_follow_tooltip(&self, frame: &mut Frame, margined_area: Rectangle, point: Point, clearance: f32, content: String) {
+        let theme = &self.settings.theme;
+        let padding = 4.0;
+        let size = theme.data_description_size;
+        let (box_area, corner) = self.follow_tooltip_layout(frame.size(), margined_area, point, clearance, &content);
+        let (x, y, box_size) = (box_area.x, box_area.y, box_area.size());
+        let line_stroke = Stroke {
+            color: theme.data_description_color,
+            width: 1.0,
+            ..Default::default()
+        };
+        frame.stroke(&Path::line(point, corner), line_stroke);
+        let box_path = Path::rectangle(Point::new(x, y), box_size);
+        frame.fill(&box_path, theme.padded_background_color);
+        frame.stroke(&box_path, line_stroke);
+        frame.fill_text(Text {
+            content,
+            position: Point::new(x + padding, y + padding),
+            color: theme.data_description_color,
+            size,
+            horizontal_alignment: HorizontalAlignment::Left,
+            vertical_alignment: VerticalAlignment::Top,
+            ..Default::default()
+        });
+    }
+
+    //The box of a tooltip following `point` on a canvas of `size`, and the box corner its leader line goes to.
+    //The box keeps `clearance` from the point, on the right and above it if there is room in the margined area.
+    fn follow_tooltip_layout(&self, size: Size, margined_area: Rectangle, point: Point, clearance: f32, content: &str) -> (Rectangle, Point) {
+        let padding = 4.0;
+        let text_size = self.settings.theme.data_description_size;
+        let box_size = Size::new(
+            text::estimate_width(content, text_size) + 2.0 * padding,
+            content.lines().count() as f32 * text_size * 1.2 + 2.0 * padding,
+        );
+        let right = point.x + clearance + box_size.width <= margined_area.x + margined_area.width;
+        let above = point.y - clearance - box_size.height >= margined_area.y;
+        let x = if right { point.x + clearance } else { point.x - clearance - box_size.width };
+        let y = if above { point.y - clearance - box_size.height } else { point.y + clearance };
+        //Boxes larger than the room on both sides are moved back into the canvas
+        let x = x.min(size.width - box_size.width).max(0.0);
+        let y = y.min(size.height - box_size.height).max(0.0);
+        //The box corner closest to the point
+        let corner = Point::new(
+            if right { x } else { x + box_size.width },
+            if above { y + box_size.height } else { y },
+        );
+        (Rectangle::new(Point::new(x, y), box_size), corner)
+    }
+
+    //The description of the selected point in a box in a corner of the margined area
+    fn draw_corner_tooltip(&self, frame: &mut Frame, margined_area: Rectangle, corner: data::Corner, content: String) {
+        let theme = &self.settings.theme;
+        let padding = 4.0;
+        let size = theme.data_description_size;
+        let box_size = Size::new(
+            text::estimate_width(&content, size) + 2.0 * padding,
+            content.lines().count() as f32 * size * 1.2 + 2.0 * padding,
+        );
+        let corner = if self.settings.rtl { corner.mirrored() } else { corner };
+        let (anchor, horizontal_alignment, vertical_alignment) = corner.anchor(margined_area, 10.0);
+        let x = match horizontal_alignment {
+            HorizontalAlignment::Left => anchor.x,
+            _ => anchor.x - box_size.width,
+        };
+        let y = match vertical_alignment {
+            VerticalAlignment::Top => anchor.y,
+            _ => anchor.y - box_size.height,
+        };
+        let box_path = Path::rectangle(Point::new(x, y), box_size);
+        frame.fill(&box_path, theme.padded_background_color);
+        frame.stroke(
+            &box_path,
+            Stroke {
+                color: theme.data_description_color,
+                width: 1.0,
+                ..Default::default()
+            },
+        );
+        frame.fill_text(Text {
+            content,
+            position: Point::new(x + padding, y + padding),
+            color: theme.data_description_color,
+            size,
+            horizontal_alignment: HorizontalAlignment::Left,
+            vertical_alignment: VerticalAlignment::Top,
+            ..Default::default()
+        });
+    }
+
+    //Whether the extremes of the plots get rings and value tags, by default only a single plot gets them
+    fn marks_extremes(&self) -> bool {
+        self.settings.mark_extremes.unwrap_or(self.data.len() == 1)
+    }
+
+    //Where the label of size `label_size` of `point` goes, given the areas taken by the labels drawn so far.
+    //The label is centered above the point at `gap`, nudged labels go below it, hidden labels get no area.
+    fn point_label_area(&self, point: Point, label_size: Size, gap: f32, taken_areas: &[Rectangle]) -> Option<Rectangle> {
+        let above = Rectangle::new(Point::new(point.x - label_size.width / 2.0, point.y - gap - label_size.height), label_size);
+        let below = Rectangle::new(Point::new(point.x - label_size.width / 2.0, point.y + gap), label_size);
+        let is_free = |area: &Rectangle| taken_areas.iter().all(|taken| taken.intersection(area).is_none());
+        match self.settings.point_label_overflow {
+            LabelOverflow::Overlap => Some(above),
+            LabelOverflow::Hide => is_free(&above).then_some(above),
+            LabelOverflow::Nudge => [above, below].iter().copied().find(|area| is_free(area)),
+        }
+    }
+
+    //The area of the normal band, over the whole width and clamped to the margined area, and its color
+    fn normal_band_area(&self, margined_area: Rectangle) -> Option<(Rectangle, Color)> {
+        let (low, high, color) = self.normal_band_opt.as_ref()?;
+        let y1 = self.map_y(low, margined_area.height).max(0.0).min(margined_area.height);
+        let y2 = self.map_y(high, margined_area.height).max(0.0).min(margined_area.height);
+        let area = Rectangle::new(
+            Point::new(margined_area.x, margined_area.y + y1.min(y2)),
+            Size::new(margined_area.width, (y1 - y2).abs()),
+        );
+        Some((area, *color))
+    }
+
+    //How many points of a plot of `plot_points` (out of `total_points` over all plots) get drawn, `None` means all of them.
+    //Progressive detail limits every plot to the current detail level, `max_total_points` shares its cap between the plots.
+    //The flag tells whether a higher detail level would draw more points: only the detail level can be refined, the cap stays.
+    fn max_drawn_points(&self, plot_points: usize, total_points: usize) -> (Option<usize>, bool) {
+        let max_detail_points_opt = self.settings.progressive_detail.map(|progressive_detail| {
+            progressive_detail
+                .initial_points
+                .saturating_mul(2usize.saturating_pow(self.detail_level.get()))
+        });
+        let capped_points_opt = self.settings.max_total_points
+            .filter(|max_total_points| total_points > *max_total_points)
+            .map(|max_total_points| (max_total_points as f64 * plot_points as f64 / total_points as f64) as usize);
+        let max_points_opt = match (max_detail_points_opt, capped_points_opt) {
+            (Some(max_detail_points), Some(capped_points)) => Some(max_detail_points.min(capped_points)),
+            (max_detail_points_opt, capped_points_opt) => max_detail_points_opt.or(capped_points_opt),
+        };
+        let refinable = matches!(max_detail_points_opt, Some(max_detail_points) if max_detail_points < plot_points
+            && capped_points_opt.map_or(true, |capped_points| max_detail_points < capped_points));
+        (max_points_opt, refinable)
+    }
+
+    //Indices of the points `draw` uses for every plot of `points`, with the flag of `max_drawn_points`
+    fn drawn_indices(&self, points: &[Vec<Point>]) -> Vec<(Vec<usize>, bool)> {
+        let total_points: usize = points.iter().map(Vec::len).sum();
+        points
+            .iter()
+            .map(|vec| {
+                let (max_points_opt, refinable) = self.max_drawn_points(vec.len(), total_points);
+                let drawn_indices = match max_points_opt {
+                    Some(max_points) => crate::math::decimate_indices(vec.len(), max_points),
+                    None => (0..vec.len()).collect(),
+                };
+                (drawn_indices, refinable)
+            })
+            .collect()
+    }
+
+    //What makes up the line of a plot with `points` drawn through `line_points`, bottom to top:
+    //the full resolution ghost (if there is one) and the runs of `line_points` between gaps, with the index of their first point.
+    //The runs are clipped to `plot_area`, so bounds tighter than the data cut the lines at the edges.
+    fn line_layers(&self, points: &[Point], line_points: &[Point], plot_area: Rectangle) -> Vec<LineLayer> {
+        let ghost_segments = self.ghost_segments(points, line_points.len(), plot_area);
+        let runs = gap_runs(line_points)
+            .into_iter()
+            .flat_map(|(offset, run)| {
+                crate::math::clip_polyline(run, plot_area)
+                    .into_iter()
+                    .map(move |(start, piece)| (offset + start, piece))
+            })
+            .collect();
+        let ghost_opt = (!ghost_segments.is_empty()).then(|| LineLayer::Ghost(ghost_segments));
+        ghost_opt.into_iter().chain(std::iter::once(LineLayer::Line(runs))).collect()
+    }
+
+    //Segments of the full resolution ghost of a plot with `points`, clipped to `plot_area`.
+    //There is only a ghost when it is enabled and fewer than all the points (`drawn_points`) make up the line.
+    fn ghost_segments(&self, points: &[Point], drawn_points: usize, plot_area: Rectangle) -> Vec<(Point, Point)> {
+        if !self.settings.show_full_resolution_ghost || drawn_points >= points.len() {
+            return Vec::new();
+        }
+        points
+            .windows(2)
+            .filter(|slice| !is_gap(&slice[0]) && !is_gap(&slice[1]))
+            .filter_map(|slice| crate::math::clip_segment(slice[0], slice[1], plot_area))
+            .collect()
+    }
+
+    //Where the line through `points` (of a plot on `axis`) crosses the crossing reference,
+    //only the crossings inside of `plot_area`
+    fn crossing_markers(&self, axis: YAxis, points: &[Point], plot_area: Rectangle) -> Vec<Point> {
+        match &self.crossing_reference_opt {
+            Some(crossing_reference) => {
+                let crossing_y = self.map_axis_y(axis, crossing_reference, plot_area.height);
+                crate::math::crossings(points, crossing_y)
+                    .into_iter()
+                    .filter(|crossing| plot_area.contains(*crossing))
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    //The watermark text in its corner of the margined area, faded by its opacity
+    fn watermark_text(&self, margined_area: Rectangle) -> Option<Text> {
+        let watermark = self.settings.watermark.as_ref()?;
+        let (position, horizontal_alignment, vertical_alignment) = watermark.corner.anchor(margined_area, 10.0);
+        Some(Text {
+            content: watermark.text.clone(),
+            position,
+            color: Color {
+                a: watermark.color.a * watermark.opacity,
+                ..watermark.color
+            },
+            size: watermark.size,
+            horizontal_alignment,
+            vertical_alignment,
+            ..Default::default()
+        })
+    }
+
+    //The swatch color and the text of the legend entry of every named plot, unnamed plots are left out
+    fn legend_entries(&self) -> Vec<(Color, String)> {
+        self.data
+            .iter()
+            .filter_map(|(plot_settings, edges)| {
+                let name = plot_settings.name.as_ref()?;
+                let text = if self.settings.legend_show_counts {
+                    format!("{} ({})", name, edges.len())
+                } else {
+                    name.clone()
+                };
+                Some((plot_settings.theme.line_color, text))
+            })
+            .collect()
+    }
+
+    //The box of the legend with `entries` in its corner of the margined area, mirrored for right to left charts
+    fn legend_area(&self, margined_area: Rectangle, legend: LegendSettings, entries: &[(Color, String)]) -> Rectangle {
+        let padding = 6.0;
+        let swatch_width = 12.0;
+        let row_height = legend.text_size + 4.0;
+        let text_width = entries
+            .iter()
+            .map(|(_color, text)| text::estimate_width(text, legend.text_size))
+            .fold(0.0, f32::max);
+        let size = Size::new(
+            swatch_width + padding + text_width + 2.0 * padding,
+            entries.len() as f32 * row_height + 2.0 * padding,
+        );
+        let corner = if self.settings.rtl {
+            legend.corner.mirrored()
+        } else {
+            legend.corner
+        };
+        let (anchor, horizontal_alignment, vertical_alignment) = corner.anchor(margined_area, 10.0);
+        let x = match horizontal_alignment {
+            HorizontalAlignment::Left => anchor.x,
+            _ => anchor.x - size.width,
+        };
+        let y = match vertical_alignment {
+            VerticalAlignment::Top => anchor.y,
+            _ => anchor.y - size.height,
+        };
+        Rectangle::new(Point::new(x, y), size)
+    }
+
+    //A swatch of the line color and the name of every named plot, in a box in a corner of the margined area
+    fn draw_legend(&self, frame: &mut Frame, margined_area: Rectangle, legend: LegendSettings) {
+        let entries = self.legend_entries();
+        if entries.is_empty() {
+            return;
+        }
+        let padding = 6.0;
+        let swatch_width = 12.0;
+        let row_height = legend.text_size + 4.0;
+        let legend_area = self.legend_area(margined_area, legend, &entries);
+        let (x, y, size) = (legend_area.x, legend_area.y, legend_area.size());
+        frame.fill(&Path::rectangle(Point::new(x, y), size), legend.background_color);
+        for (i, (color, text)) in entries.into_iter().enumerate() {
+            let row_center = y + padding + (i as f32 + 0.5) * row_height;
+            frame.stroke(
+                &Path::line(Point::new(x + padding, row_center), Point::new(x + padding + swatch_width, row_center)),
+                Stroke {
+                    color,
+                    width: 3.0,
+                    ..Default::default()
+                },
+            );
+            frame.fill_text(Text {
+                content: text,
+                position: Point::new(x + 2.0 * padding + swatch_width, row_center),
+                color: legend.text_color,
+                size: legend.text_size,
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Center,
+                ..Default::default()
+            });
+        }
+    }
+
+    //Y coordinate (inside of an area with the given height) where area fills end:
+    //the zero line if it is inside of the y range, otherwise the closest y bound
+    fn baseline_y(&self, axis: YAxis, height: f32) -> f32 {
+        let (min_y_value, max_y_value, _total_y_distance) = self.y_range(axis);
+        match YV::zero() {
+            Some(zero) if zero.compare_value(max_y_value) == Ordering::Greater => 0.0,
+            Some(zero) if zero.compare_value(min_y_value) != Ordering::Less => self.map_axis_y(axis, &zero, height),
+            _ => height,
+        }
+    }
+
+    //Y coordinate (relative to the canvas) of the x axis when it is moved to the zero line
+    fn x_axis_y(&self, margined_area: Rectangle) -> Option<f32> {
+        if !self.settings.x_axis_at_zero {
+            return None;
+        }
+        let zero = YV::zero()?;
+        let inside = zero.compare_value(&self.min_y_value) != Ordering::Less
+            && zero.compare_value(&self.max_y_value) != Ordering::Greater;
+        inside.then(|| margined_area.y + self.map_y(&zero, margined_area.height))
+    }
+
+    //Labels of the secondary axis are drawn on the other side with only a tick mark, the gridlines follow the primary one
+    fn y_label_geometry(
+        &self,
+        size: Size,
+        padded_area: Rectangle,
+        margined_area: Rectangle,
+        axis: YAxis,
+        y: f32,
+        text: &str,
+    ) -> LabelGeometry {
+        let theme = &self.settings.theme;
+        let width = size.width;
+        let margined_right = margined_area.x + margined_area.width;
+        //Tick mark (inside of the margin), gridline, and the mirrored tick mark on the right (unless the secondary axis is there)
+        let mut segments = Vec::new();
+        if theme.y_ticks {
+            segments.push((padded_area.x, margined_area.x));
+        }
+        if axis == YAxis::Primary {
+            if theme.y_gridlines {
+                segments.push((margined_area.x, margined_right));
+            }
+            if theme.y_ticks && theme.mirror_ticks && self.secondary_y_range_opt.is_none() {
+                segments.push((margined_right, width - padded_area.x));
+            }
+        }
+        //Right to left charts have the y axis on the right, and the secondary one on the left
+        let flipped = self.settings.rtl != (axis == YAxis::Secondary);
+        let mirror = |x: f32| if flipped { width - x } else { x };
+        let line_y = if theme.crisp_gridlines {
+            crate::math::snap_to_pixel(y, theme.y_label_line_width)
+        } else {
+            y
+        };
+        let lines = segments
+            .into_iter()
+            .map(|(x1, x2)| (Point::new(mirror(x1), line_y), Point::new(mirror(x2), line_y)))
+            .collect();
+        let alignment = theme.y_label_alignment;
+        let x = match alignment.horizontal {
+            HorizontalAlignment::Left => 5.0,
+            HorizontalAlignment::Center => padded_area.x / 2.0,
+            HorizontalAlignment::Right => padded_area.x - 5.0,
+        };
+        let horizontal_alignment = if flipped {
+            mirror_alignment(alignment.horizontal)
+        } else {
+            alignment.horizontal
+        };
+        LabelGeometry {
+            lines,
+            text: Text {
+                content: format!("{}", text),
+                color: theme.y_label_text_color,
+                position: Point::new(mirror(x), y),
+                horizontal_alignment,
+                vertical_alignment: alignment.vertical,
+                size: theme.y_label_text_size,
+                ..Default::default()
+            },
+            rotation_anchor_opt: None,
+        }
+    }
+
+    fn x_label_geometry(
+        &self,
+        size: Size,
+        padded_area: Rectangle,
+        margined_area: Rectangle,
+        x: f32,
+        text: &str,
+    ) -> LabelGeometry {
+        let theme = &self.settings.theme;
+        let height = size.height;
+        let margined_bottom = margined_area.y + margined_area.height;
+        //The tick mark and the text move up together with the axis
+        let shift = self.x_axis_y(margined_area).map_or(0.0, |axis_y| axis_y - margined_bottom);
+        //Tick mark (inside of the margin), gridline, and the mirrored tick mark on the top
+        let mut segments = Vec::new();
+        if theme.x_ticks {
+            segments.push((height - padded_area.y + shift, margined_bottom + shift));
+        }
+        if theme.x_gridlines {
+            segments.push((margined_bottom, margined_area.y));
+        }
+        if theme.x_ticks && theme.mirror_ticks {
+            segments.push((margined_area.y, padded_area.y));
+        }
+        let line_x = if theme.crisp_gridlines {
+            crate::math::snap_to_pixel(x, theme.x_label_line_width)
+        } else {
+            x
+        };
+        let lines = segments
+            .into_iter()
+            .map(|(y1, y2)| (Point::new(line_x, y1), Point::new(line_x, y2)))
+            .collect();
+        let alignment = theme.x_label_alignment;
+        let rotation = self.settings.x_label_rotation;
+        if rotation != 0.0 {
+            //The end of the text closest to the tick is at the tick
+            let horizontal_alignment = if rotation < 0.0 {
+                HorizontalAlignment::Right
+            } else {
+                HorizontalAlignment::Left
+            };
+            return LabelGeometry {
+                lines,
+                text: Text {
+                    content: format!("{}", text),
+                    color: theme.x_label_text_color,
+                    position: Point::ORIGIN,
+                    horizontal_alignment,
+                    vertical_alignment: VerticalAlignment::Center,
+                    size: theme.x_label_text_size,
+                    ..Default::default()
+                },
+                rotation_anchor_opt: Some(Point::new(x, shift + height - padded_area.y + 5.0)),
+            };
+        }
+        let y = shift + match alignment.vertical {
+            VerticalAlignment::Top => height - padded_area.y + 5.0,
+            VerticalAlignment::Center => height - padded_area.y / 2.0,
+            VerticalAlignment::Bottom => height - 5.0,
+        };
+        LabelGeometry {
+            lines,
+            text: Text {
+                content: format!("{}", text),
+                color: theme.x_label_text_color,
+                position: Point::new(x, y),
+                horizontal_alignment: alignment.horizontal,
+                vertical_alignment: alignment.vertical,
+                size: theme.x_label_text_size,
+                ..Default::default()
+            },
+            rotation_anchor_opt: None,
+        }
+    }
+
+    //Opacity factor of the colors of a plot: under `SelectionEmphasis::DimOthers` the plots other than the selected one fade out
+    fn emphasis_alpha(&self, plot_index: usize, selected_plot_opt: Option<usize>) -> f32 {
+        let dimmed = self.settings.selection_emphasis == SelectionEmphasis::DimOthers
+            && selected_plot_opt.map_or(false, |selected_plot_index| selected_plot_index != plot_index);
+        if dimmed { 0.3 } else { 1.0 }
+    }
+
+    //Area fill polygons between every run of at least two points and `baseline`, with the fill blend applied to their color
+    fn fill_geometry(&self, runs: &[(usize, Vec<Point>)], baseline: f32, fill_color: Color) -> Vec<(Vec<Point>, Color)> {
+        let color = self.settings.fill_blend.apply(fill_color);
+        runs.iter()
+            .filter(|(_offset, run)| run.len() >= 2)
+            .map(|(_offset, run)| {
+                let mut polygon = Vec::with_capacity(run.len() + 2);
+                polygon.push(Point::new(run[0].x, baseline));
+                polygon.extend(run.iter().cloned());
+                polygon.push(Point::new(run[run.len() - 1].x, baseline));
+                (polygon, color)
+            })
+            .collect()
+    }
+
+    fn draw_label(&self, frame: &mut Frame, geometry: LabelGeometry, line_color: Color, line_width: f32) {
+        let stroke = || Stroke {
+            color: line_color,
+            width: line_width,
+            ..Default::default()
+        };
+        let LabelGeometry { lines, text, rotation_anchor_opt } = geometry;
+        for (from, to) in lines {
+            frame.stroke(&Path::line(from, to), stroke());
+        }
+        match rotation_anchor_opt {
+            Some(anchor) => frame.with_save(|frame| {
+                frame.translate(Vector::new(anchor.x, anchor.y));
+                frame.rotate(self.settings.x_label_rotation.to_radians());
+                frame.fill_text(text);
+            }),
+            None => frame.fill_text(text),
+        }
+    }
+}
+
+//Tick marks and the gridline of a label, and its text which is rotated around `rotation_anchor_opt` if there is one
+struct LabelGeometry {
+    lines: Vec<(Point, Point)>,
+    text: Text,
+    rotation_anchor_opt: Option<Point>,
+}
+
+//What `draw` puts on the canvas, bottom to top
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layer {
+    Backgrounds, //The full and the padded backgrounds
+    MarginedBackground,
+    Grid, //The gridlines and the labels
+    Data,
+}
+
+//The order of the layers, only the gridlines move
+fn layer_order(grid_layer: GridLayer) -> [Layer; 4] {
+    match grid_layer {
+        GridLayer::BelowMarginedBackground => [Layer::Backgrounds, Layer::Grid, Layer::MarginedBackground, Layer::Data],
+        GridLayer::BelowData => [Layer::Backgrounds, Layer::MarginedBackground, Layer::Grid, Layer::Data],
+        GridLayer::AboveData => [Layer::Backgrounds, Layer::MarginedBackground, Layer::Data, Layer::Grid],
+    }
+}
+
+//Whether the gridlines are drawn right after `layer`
+fn grid_follows(grid_layer: GridLayer, layer: Layer) -> bool {
+    layer_order(grid_layer).windows(2).any(|pair| pair == [layer, Layer::Grid])
+}
+
+//A part of the line of a plot, see `Chart::line_layers`
+#[derive(Debug, Clone, PartialEq)]
+enum LineLayer {
+    Ghost(Vec<(Point, Point)>),
+    Line(Vec<(usize, Vec<Point>)>), //Every run is drawn like a separate line
+}
+
+//The smallest preferred step not below `step`, or a multiple of the biggest one
+fn snap_step(preferred_steps: Vec<f32>, step: f32) -> f32 {
+    match preferred_steps.iter().find(|preferred_step| **preferred_step >= step) {
+        Some(preferred_step) => *preferred_step,
+        None => match preferred_steps.last() {
+            Some(biggest_step) if *biggest_step > 0.0 => (step / biggest_step).ceil() * biggest_step,
+            _ => step,
+        },
+    }
+}
+
+fn mirror_alignment(alignment: HorizontalAlignment) -> HorizontalAlignment {
+    match alignment {
+        HorizontalAlignment::Left => HorizontalAlignment::Right,
+        HorizontalAlignment::Center => HorizontalAlignment::Center,
+        HorizontalAlignment::Right => HorizontalAlignment::Left,
+    }
+}
+
+//The values inside of `[min; max]`
+fn within<V: AxisValue>(values: &[V], min: &V, max: &V) -> Vec<V> {
+    values
+        .iter()
+        .filter(|value| value.compare_value(min) != Ordering::Less && value.compare_value(max) != Ordering::Greater)
+        .cloned()
+        .collect()
+}
+
+//Whether the value is below the minimum or above the maximum, missing bounds don't limit anything
+fn is_outside<V: AxisValue>(value: &V, min_opt: &Option<V>, max_opt: &Option<V>) -> bool {
+    matches!(min_opt, Some(min) if value.compare_value(min) == Ordering::Less)
+        || matches!(max_opt, Some(max) if value.compare_value(max) == Ordering::Greater)
+}
+
+//Minimum distance in pixels between the labels along an axis of `length`,
+//spreading `count_opt` labels over it when set
+fn label_distance(count_opt: Option<usize>, min_label_distance: DistanceValue, length: f32, size: Size) -> f32 {
+    match count_opt {
+        Some(count) => length / (count.max(2) - 1) as f32,
+        None => min_label_distance.get(size),
+    }
+}
+
+//Largest-Triangle-Three-Buckets on the distances from the first point
+fn downsampled<XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>>(edges: Vec<(XD, YD)>, target: usize) -> Vec<(XD, YD)> {
+    if edges.len() <= target {
+        return edges;
+    }
+    let (first_x, first_y) = (edges[0].0.value().clone(), edges[0].1.value().clone());
+    let coords: Vec<(f32, f32)> = edges
+        .iter()
+        .map(|(xd, yd)| (first_x.signed_distance_to(xd.value()), first_y.signed_distance_to(yd.value())))
+        .collect();
+    let indices = crate::math::lttb_indices(&coords, target);
+    let mut edges: Vec<Option<(XD, YD)>> = edges.into_iter().map(Some).collect();
+    indices.iter().filter_map(|index| edges[*index].take()).collect()
+}
+
+//The highest and the lowest of the points inside of `area`, with their indices.
+//Y grows downwards, so the highest point has the smallest y.
+fn extreme_points(points: &[Point], area: Rectangle) -> Option<((usize, Point), (usize, Point))> {
+    let by_y = |(_index1, p1): &(usize, Point), (_index2, p2): &(usize, Point)| p1.y.total_cmp(&p2.y);
+    let inside = || points.iter().copied().enumerate().filter(|(_index, point)| area.contains(*point));
+    Some((inside().min_by(by_y)?, inside().max_by(by_y)?))
+}
+
+//Points of values which aren't finite are gaps, they aren't drawn and break the line
+fn is_gap(point: &Point) -> bool {
+    !point.x.is_finite() || !point.y.is_finite()
+}
+
+//The runs of points between gaps, with the index of their first point
+fn gap_runs(points: &[Point]) -> Vec<(usize, &[Point])> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for (index, point) in points.iter().enumerate() {
+        if is_gap(point) {
+            if index > start {
+                runs.push((start, &points[start..index]));
+            }
+            start = index + 1;
+        }
+    }
+    if points.len() > start {
+        runs.push((start, &points[start..]));
+    }
+    runs
+}
+
+//The smallest and the biggest of the finite values, `None` if there are none
+fn value_range<'a, V: AxisValue + 'a>(values: impl Iterator<Item = &'a V>) -> Option<(V, V)> {
+    let mut range_opt: Option<(&V, &V)> = None;
+    for value in values.filter(|value| value.is_finite()) {
+        range_opt = Some(match range_opt {
+            None => (value, value),
+            Some((min, max)) => (
+                if value.compare_value(min) == Ordering::Less { value } else { min },
+                if value.compare_value(max) == Ordering::Greater { value } else { max },
+            ),
+        });
+    }
+    range_opt.map(|(min, max)| (min.clone(), max.clone()))
+}
+
+//The bounds moved outward by `fraction` of their span, a bound which can't be moved stays
+fn padded<V: AxisValue>(min: &V, max: &V, fraction: f32) -> (V, V) {
+    let padding = min.distance_to(max) * fraction;
+    (
+        min.add(-padding).unwrap_or_else(|| min.clone()),
+        max.add(padding).unwrap_or_else(|| max.clone()),
+    )
+}
+
+//Bounds including `value` too, with `headroom` of the new range beyond it on its side
+fn expanded<V: AxisValue>(min: &V, max: &V, value: &V, headroom: f32) -> (V, V) {
+    let new_min = if value.compare_value(min) == Ordering::Less { value } else { min };
+    let new_max = if value.compare_value(max) == Ordering::Greater { value } else { max };
+    let padding = new_min.distance_to(new_max) * headroom;
+    let padded_min = if value.compare_value(min) == Ordering::Less {
+        new_min.add(-padding).unwrap_or_else(|| new_min.clone())
+    } else {
+        new_min.clone()
+    };
+    let padded_max = if value.compare_value(max) == Ordering::Greater {
+        new_max.add(padding).unwrap_or_else(|| new_max.clone())
+    } else {
+        new_max.clone()
+    };
+    (padded_min, padded_max)
+}
+
+//Where a drag started and the bounds at that moment, the window moves relative to them
+struct PanState<XV, YV> {
+    anchor: Point,
+    view_state: ViewState<XV, YV>,
+}
+
+//The bounds before and after `Chart::set_view_state`
+struct BoundsAnimation<XV, YV> {
+    from: ViewState<XV, YV>,
+    to: ViewState<XV, YV>,
+    started: Instant,
+}
+
+//The value `t` (in `[0; 1]`) of the way from `from` to `to`, `to` if the value type can't represent it
+fn interpolate_value<V: AxisValue>(from: &V, to: &V, t: f32) -> V {
+    if t >= 1.0 {
+        return to.clone();
+    }
+    from.add(from.signed_distance_to(to) * t).unwrap_or_else(|| to.clone())
+}
+
+//Two clicks closer together than this make a double-click
+const DOUBLE_CLICK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(400);
+
+//Points closer than this (in pixels) count as being at the same place
+const COINCIDENCE_DISTANCE: f32 = 0.5;
+
+//An ellipse in distances from a reference point, so it can be projected like the data
+struct ConfidenceEllipse<XV, YV> {
+    reference: (XV, YV),
+    center: (f32, f32),
+    semi_axes: (f32, f32),
+    angle: f32,
+    theme: PlotThemeSettings,
+}
+
+//Distance from the axis minimum to the origin of the scale (zero if the value type has one, else the minimum)
+fn origin_offset<V: AxisValue>(min: &V) -> f32 {
+    V::zero()
+        .map(|zero| min.signed_distance_to(&zero))
+        .unwrap_or(0.0)
+}
+
+//Maps `distance` (from the axis minimum) through the scale to a coordinate inside of `[0; length]`
+fn map_distance<V: AxisValue>(scale: ScaleKind, min: &V, total_distance: f32, distance: f32, length: f32) -> f32 {
+    //Linear scales don't depend on where zero is, going through it would round away the distances far from zero
+    if matches!(scale, ScaleKind::Linear) {
+        return crate::math::map_inverval_value(distance, (0.0, total_distance), (0.0, length));
+    }
+    let origin_offset = origin_offset(min);
+    let scaled = |distance: f32| scale.transform(distance - origin_offset) - scale.transform(-origin_offset);
+    crate::math::map_inverval_value(
+        scaled(distance),
+        (0.0, scaled(total_distance)),
+        (0.0, length),
+    )
+}
+
+//Inverse of `map_distance`
+fn unmap_distance<V: AxisValue>(scale: ScaleKind, min: &V, total_distance: f32, coord: f32, length: f32) -> f32 {
+    if matches!(scale, ScaleKind::Linear) {
+        return crate::math::map_inverval_value(coord, (0.0, length), (0.0, total_distance));
+    }
+    let origin_offset = origin_offset(min);
+    let scaled = crate::math::map_inverval_value(
+        coord,
+        (0.0, length),
+        (0.0, scale.transform(total_distance - origin_offset) - scale.transform(-origin_offset)),
+    );
+    scale.inverse(scaled + scale.transform(-origin_offset)) + origin_offset
+}
+
+//Tick values of a non-linear scale strictly between the axis minimum and maximum,
+//skipping those closer than `min_label_distance` pixels to the previous tick or to the ends
+fn scale_tick_values<V: AxisValue>(
+    scale: ScaleKind,
+    min: &V,
+    total_distance: f32,
+    length: f32,
+    min_label_distance: f32,
+) -> Vec<V> {
+    let origin_offset = origin_offset(min);
+    let mut last_coord = 0.0;
+    let mut result = Vec::new();
+    for offset in scale.tick_offsets(-origin_offset, total_distance - origin_offset) {
+        let distance = offset + origin_offset;
+        let coord = map_distance(scale, min, total_distance, distance, length);
+        if coord - last_coord < min_label_distance || length - coord < min_label_distance {
+            continue;
+        }
+        if let Some(value) = min.add(distance) {
+            result.push(value);
+            last_coord = coord;
+        }
+    }
+    result
+}
+
+//Linearly interpolates the y value at `x` between the two neighbouring edges.
+//Expects edges sorted by x, returns `None` when `x` is outside of them.
+pub fn interpolate_y<XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>>(
+    edges: &[(XD, YD)],
+    x: &XV,
+) -> Option<YV> {
+    if let [(xd, yd)] = edges {
+        return (xd.value().compare_value(x) == Ordering::Equal).then(|| yd.value().clone());
+    }
+    edges.windows(2).find_map(|slice| {
+        let (xd1, yd1) = &slice[0];
+        let (xd2, yd2) = &slice[1];
+        let x1 = xd1.value();
+        let x2 = xd2.value();
+        if x1.compare_value(x) == Ordering::Greater || x.compare_value(x2) == Ordering::Greater {
+            return None;
+        }
+        let span = x1.signed_distance_to(x2);
+        if span == 0.0 {
+            return Some(yd1.value().clone());
+        }
+        let t = x1.signed_distance_to(x) / span;
+        yd1.value().add(t * yd1.value().signed_distance_to(yd2.value()))
+    })
+}
+
+//Path of a point marker, `size` is the radius of the circle around it
+fn marker_path(marker: Marker, center: Point, size: f32) -> Path {
+    match marker_vertices(marker, size) {
+        Some(vertices) => Path::new(|builder| {
+            builder.move_to(Point::new(center.x + vertices[0].0, center.y + vertices[0].1));
+            for (dx, dy) in vertices[1..].iter() {
+                builder.line_to(Point::new(center.x + dx, center.y + dy));
+            }
+            builder.close();
+        }),
+        None => Path::circle(center, size),
+    }
+}
+
+//Corners of the polygon of a marker relative to its center, `None` for circles
+fn marker_vertices(marker: Marker, size: f32) -> Option<Vec<(f32, f32)>> {
+    match marker {
+        Marker::Circle => None,
+        Marker::Square => {
+            let half = size / std::f32::consts::SQRT_2;
+            Some(vec![(-half, -half), (half, -half), (half, half), (-half, half)])
+        }
+        Marker::Triangle => {
+            let (sin, cos) = (std::f32::consts::PI / 6.0).sin_cos();
+            Some(vec![(0.0, -size), (size * cos, size * sin), (-size * cos, size * sin)])
+        }
+        Marker::Diamond => Some(vec![(0.0, -size), (size, 0.0), (0.0, size), (-size, 0.0)]),
+        Marker::Cross => {
+            //A plus sign with arms a third of the size wide
+            let arm = size / 3.0;
+            Some(vec![
+                (-arm, -size), (arm, -size), (arm, -arm), (size, -arm),
+                (size, arm), (arm, arm), (arm, size), (-arm, size),
+                (-arm, arm), (-size, arm), (-size, -arm), (-arm, -arm),
+            ])
+        }
+    }
+}
+
+//The corner radius actually used for `area`, `None` when the corners stay sharp.
+//Never more than half the shorter side, so the arcs don't overlap.
+fn rounded_corner_radius(area: Rectangle, radius: f32) -> Option<f32> {
+    (radius > 0.0).then(|| radius.min(area.width / 2.0).min(area.height / 2.0))
+}
+
+//Sharp rectangle when `radius` is zero, otherwise a rectangle with rounded corners
+fn background_path(area: Rectangle, radius: f32) -> Path {
+    let radius = match rounded_corner_radius(area, radius) {
+        Some(radius) => radius,
+        None => return Path::rectangle(area.position(), area.size()),
+    };
+    let left = area.x;
+    let top = area.y;
+    let right = area.x + area.width;
+    let bottom = area.y + area.height;
+    Path::new(|builder| {
+        builder.move_to(Point::new(left + radius, top));
+        builder.arc_to(Point::new(right, top), Point::new(right, bottom), radius);
+        builder.arc_to(Point::new(right, bottom), Point::new(left, bottom), radius);
+        builder.arc_to(Point::new(left, bottom), Point::new(left, top), radius);
+        builder.arc_to(Point::new(left, top), Point::new(right, top), radius);
+        builder.close();
+    })
+}
+
+impl <XV: data::AxisValue, YV: data::AxisValue, XD: data::AxisData<XV>, YD: data::AxisData<YV>> Program<data::Message> for Chart<XV, YV, XD, YD> {
+    fn draw(&self, bounds: Rectangle, cursor: Cursor) -> Vec<Geometry> {
+        let theme = self.settings.theme.clone();
+        
+        let size = bounds.size();
+        let width = bounds.width;
+        let height = bounds.height;
+
+        let (ptop, pright, pbottom, pleft) = self.settings.padding.get(size);
+        let (mtop, mright, mbottom, mleft) = self.settings.margin.get(size);
+
+        let full_area = Rectangle::new(Point::ORIGIN, size);
+        let padded_area = self.settings.padding.transform(full_area);
+        let margined_area = self.settings.margin.transform(padded_area);
+
+        let cursor_position_opt = cursor.position_in(&bounds);
+        let padded_cursor_position_opt =
+            cursor_position_opt.map(|cp| Point::new(cp.x - padded_area.x, cp.y - padded_area.y));
+        let margined_cursor_position_opt = cursor_position_opt
+            .map(|cp| Point::new(cp.x - margined_area.x, cp.y - margined_area.y));
+
+        let started = Instant::now();
+        let result = self.cache.draw(size, |frame| {
+            frame.fill(
+                &Path::rectangle(full_area.position(), full_area.size()),
+                self.settings.theme.background_color,
+            );
+            frame.fill(
+                &background_path(padded_area, theme.background_corner_radius),
+                self.settings.theme.padded_background_color,
+            );
+            if grid_follows(self.settings.grid_layer, Layer::Backgrounds) {
+                self.draw_grid(frame, padded_area, margined_area, cursor_position_opt);
+            }
+            self.settings.theme.margined_background_color.iter().for_each(|margined_background_color| {
+                frame.fill(
+                    &background_path(margined_area, theme.background_corner_radius),
+                    *margined_background_color,
+                );
+            });
+
+            //Draw name
+            self.settings.title
+                .as_ref()
+                .iter()
+                .for_each(|title| {
+                    let (position, _subtitle_position, horizontal_alignment) = self.title_positions(size);
+                    frame.fill_text(Text {
+                        content: (*title).clone(),
+                        position,
+                        color: self.settings.theme.title_color,
+                        size: self.settings.theme.title_size,
+                        horizontal_alignment,
+                        vertical_alignment: VerticalAlignment::Center,
+                        ..Default::default()
+                    });
+        
+                });
+            if let Some(subtitle) = &self.settings.subtitle {
+                let (_title_position, position, horizontal_alignment) = self.title_positions(size);
+                frame.fill_text(Text {
+                    content: subtitle.clone(),
+                    position,
+                    color: self.settings.theme.title_color,
+                    size: self.settings.theme.subtitle_size,
+                    horizontal_alignment,
+                    vertical_alignment: VerticalAlignment::Center,
+                    ..Default::default()
+                });
+            }
+            self.draw_axis_titles(frame, margined_area);
+            if grid_follows(self.settings.grid_layer, Layer::MarginedBackground) {
+                self.draw_grid(frame, padded_area, margined_area, cursor_position_opt);
+            }
+
+            //Draw the normal band, clamped to the margined area
+            if let Some((band_area, color)) = self.normal_band_area(margined_area) {
+                frame.fill(&Path::rectangle(band_area.position(), band_area.size()), color);
+            }
+
+            //Draw the horizontal reference lines
+            for (value, color, label) in &self.h_reference_lines {
+                if value.compare_value(&self.min_y_value) == Ordering::Less || value.compare_value(&self.max_y_value) == Ordering::Greater {
+                    continue;
+                }
+                let y = margined_area.y + self.map_y(value, margined_area.height);
+                frame.stroke(
+                    &Path::line(Point::new(margined_area.x, y), Point::new(margined_area.x + margined_area.width, y)),
+                    Stroke {
+                        color: *color,
+                        width: 1.0,
+                        ..Default::default()
+                    },
+                );
+                if !label.is_empty() {
+                    frame.fill_text(Text {
+                        content: label.clone(),
+                        position: Point::new(margined_area.x + 2.0, y - 2.0),
+                        color: *color,
+                        size: theme.y_label_text_size,
+                        horizontal_alignment: HorizontalAlignment::Left,
+                        vertical_alignment: VerticalAlignment::Bottom,
+                        ..Default::default()
+                    });
+                }
+            }
+
+            //Draw the identity line
+            if let Some(identity_line_color) = self.settings.identity_line {
+                let identity_points = self.identity_points(margined_area.size());
+                if identity_points.len() >= 2 {
+                    frame.with_save(|frame| {
+                        frame.translate(Vector::new(margined_area.x, margined_area.y));
+                        frame.stroke(
+                            &Path::new(|builder| {
+                                builder.move_to(identity_points[0]);
+                                identity_points[1..].iter().for_each(|point| builder.line_to(*point));
+                            }),
+                            Stroke {
+                                color: identity_line_color,
+                                width: 1.0,
+                                ..Default::default()
+                            },
+                        );
+                    });
+                }
+            }
 
             let points = self.points(margined_area.size());
 
-            //Unreadable shit which finds the selected edge
-            let selected_point_opt: Option<(&data::PlotSettings, &(Point, XD, YD))> = margined_cursor_position_opt
-                .map(|margined_cursor_position| {
-                    points
-                        .iter()
-                        .filter_map(|(settings, vec)| {
-                            let iter = vec.iter();
-                            let mapped = iter.map(|tuple| {
-                                (tuple, margined_cursor_position.distance(tuple.0))
-                            });
-                            let filtered = mapped
-                                .filter(|(_tuple, distance)| *distance <= 14.0);
-                            filtered.min_by(|(_tuple1, f1), (_tuple2, f2)| f1.total_cmp(f2))
-                                .map(|(tuple, distance)| (settings, tuple, distance))
-                        })
-                        .min_by(|(_settings1, _tuple1, distance1), (_settings2, _tuple2, distance2)| distance1.total_cmp(distance2))
-                        .map(|(settings, tuple, _distance)| (settings, tuple))
-                })
-                .flatten();
-            //Unreadable shit which finds the selected vertice
-            let selected_plot_opt: Option<&data::PlotSettings> = selected_point_opt
-                .map(|(settings, _)| settings)
+            let selected_point_opt: Option<(usize, usize)> = margined_cursor_position_opt
+                .and_then(|margined_cursor_position| self.find_selected_point(&points, margined_cursor_position, margined_area.size()));
+            let selected_plot_opt: Option<usize> = selected_point_opt
+                .map(|(plot_index, _point_index)| plot_index)
                 .or_else(|| margined_cursor_position_opt
-                    .map(|margined_cursor_position| {
-                        points
-                            .iter()
-                            .filter_map(|(settings, vec)| {
-                                let windows = vec.windows(2);
-                                let mapped = windows.map(|slice| {
-                                    let (p1, _xd1, _yd1) = &slice[0];
-                                    let (p2, _xd2, _yd2) = &slice[1];
-                                    crate::math::point_to_interval_distance(margined_cursor_position, *p1, *p2)
-                                });
-                                let filtered = mapped
-                                    .filter(|distance| *distance <= 6.0);
-                                filtered.min_by(|f1, f2| f1.total_cmp(f2))
-                                    .map(|distance| (settings, distance))
-                            })
-                            .min_by(|(_settings1, distance1), (_settings2, distance2)| distance1.total_cmp(distance2))
-                            .map(|(settings, _distance)| settings)
-                    }).flatten());
+                    .and_then(|margined_cursor_position| self.find_selected_plot(&points, margined_cursor_position)));
             
             selected_point_opt
+                .filter(|_selected_point| self.settings.tooltip_placement == TooltipPlacement::Header)
                 .iter()
-                .for_each(|(_settings, (_p, xd, yd))| {
-                    let mut content = String::new();
-                    content.push_str(&xd.description());
-                    content.push('\n');
-                    content.push_str(&yd.description());
+                .for_each(|selected_point| {
+                    let content = self.tooltip_content(&points, *selected_point);
+                    let (x, horizontal_alignment) = if self.settings.rtl {
+                        (pleft, HorizontalAlignment::Left)
+                    } else {
+                        (padded_area.width + pleft, HorizontalAlignment::Right)
+                    };
                     frame.fill_text(Text {
                         content,
-                        position: Point::new(padded_area.width + pleft, ptop / 2.0),
+                        position: Point::new(x, ptop / 2.0),
                         color: theme.data_description_color,
                         size: theme.data_description_size,
-                        horizontal_alignment: HorizontalAlignment::Right,
+                        horizontal_alignment,
                         vertical_alignment: VerticalAlignment::Center,
                         ..Default::default()
                     });
                 });
 
+            let mut decimated = false;
+            let plot_drawn_indices = self.drawn_indices(&points);
+
+            let mark_extremes = self.marks_extremes();
+            //Areas taken by the point labels drawn so far, over all plots
+            let mut point_label_areas: Vec<Rectangle> = Vec::new();
+
+            //The iced 0.3 frame can't clip (`Frame::with_clip` comes with iced 0.4), so the plots are cut by hand:
+            //lines and fills are clipped to the plot area, and markers, crossings and extremes are only drawn
+            //for points inside of it. Markers of points right at the edge can still reach into the margin.
             frame.with_save(|frame| {
                 frame.translate(Vector::new(margined_area.x, margined_area.y));
-                for (plot_settings, vec) in points.iter() {
-                    let line_color = plot_settings.theme.line_color;
-                    let point_color = plot_settings.theme.point_color;
-                    let line_selected = matches!(selected_plot_opt, Some(r) if std::ptr::eq(r, plot_settings));
+                for (plot_index, ((plot_settings, edges), vec)) in self.data.iter().zip(points.iter()).enumerate() {
+                    let line_selected = selected_plot_opt == Some(plot_index);
+                    let emphasis = self.settings.selection_emphasis;
+                    let alpha = self.emphasis_alpha(plot_index, selected_plot_opt);
+                    let dim = |color: Color| Color {
+                        a: color.a * alpha,
+                        ..color
+                    };
+                    let line_color = if line_selected && emphasis == SelectionEmphasis::Recolor {
+                        plot_settings.theme.selected_line_color.unwrap_or(plot_settings.theme.line_color)
+                    } else {
+                        plot_settings.theme.line_color
+                    };
+                    let line_color = dim(line_color);
+                    //Color of the piece `index` of `count` pieces of the line, a recolored selected line keeps its color
+                    let line_gradient_opt = plot_settings.theme.line_gradient
+                        .filter(|_gradient| !(line_selected && emphasis == SelectionEmphasis::Recolor));
+                    let line_color_at = |index: usize, count: usize| match line_gradient_opt {
+                        Some((from, to)) => {
+                            let t = if count > 1 { index as f32 / (count - 1) as f32 } else { 0.0 };
+                            dim(crate::math::interpolate_color(from, to, t))
+                        }
+                        None => line_color,
+                    };
+                    let point_color = dim(plot_settings.theme.point_color);
+                    let point_color_at = |point_index: usize| {
+                        plot_settings.point_colors
+                            .as_ref()
+                            .and_then(|point_colors| point_colors.get(point_index))
+                            .map_or(point_color, |color| dim(*color))
+                    };
                     let line_size = if line_selected {
                         plot_settings.line_size2
                     } else {
@@ -486,30 +2812,338 @@ impl <XV: data::AxisValue, YV: data::AxisValue, XD: data::AxisData<XV>, YD: data
                     };
                     let point_size = if line_selected { plot_settings.point_size2 } else { plot_settings.point_size1 };
                     let selected_point_size = plot_settings.point_size3;
-                    //Draw lines
-                    for slice in vec.windows(2) {
-                        let (p1, _xd1, _yd1) = slice[0].to_owned();
-                        let (p2, _xd2, _yd2) = slice[1].to_owned();
+                    let (drawn_indices, refinable) = &plot_drawn_indices[plot_index];
+                    decimated |= *refinable;
+                    let drawn_points: Vec<Point> = drawn_indices.iter().map(|point_index| vec[*point_index]).collect();
+                    //Dense plots are drawn as one vertical bar per pixel column
+                    let aggregated_points_opt = if self.settings.pixel_aggregate {
+                        crate::math::aggregate_columns(&drawn_points)
+                    } else {
+                        None
+                    };
+                    let line_points = aggregated_points_opt.as_ref().unwrap_or(&drawn_points);
+                    let plot_area = Rectangle::new(Point::ORIGIN, margined_area.size());
+                    for line_layer in self.line_layers(vec, line_points, plot_area) {
+                        match line_layer {
+                            //Draw the full resolution ghost
+                            LineLayer::Ghost(segments) => {
+                                for (p1, p2) in segments {
+                                    frame.stroke(
+                                        &Path::line(p1, p2),
+                                        Stroke {
+                                            color: Color {
+                                                a: line_color.a * 0.2,
+                                                ..line_color
+                                            },
+                                            width: 1.0,
+                                            ..Default::default()
+                                        },
+                                    );
+                                }
+                            }
+                            LineLayer::Line(runs) => {
+                                //Draw area fill
+                                if let Some(fill_color) = plot_settings.theme.fill_color {
+                                    let baseline = self.baseline_y(plot_settings.axis, margined_area.height);
+                                    for (polygon, color) in self.fill_geometry(&runs, baseline, dim(fill_color)) {
+                                        frame.fill(
+                                            &Path::new(|builder| {
+                                                builder.move_to(polygon[0]);
+                                                polygon[1..].iter().for_each(|point| builder.line_to(*point));
+                                                builder.close();
+                                            }),
+                                            color,
+                                        );
+                                    }
+                                }
+                                //Draw glow
+                                if line_selected && emphasis == SelectionEmphasis::Glow {
+                                    for (_offset, run) in runs.iter() {
+                                        for slice in run.windows(2) {
+                                            frame.stroke(
+                                                &Path::line(slice[0], slice[1]),
+                                                Stroke {
+                                                    color: Color {
+                                                        a: line_color.a * 0.3,
+                                                        ..line_color
+                                                    },
+                                                    width: line_size * 3.0,
+                                                    ..Default::default()
+                                                },
+                                            );
+                                        }
+                                    }
+                                }
+                                //Draw lines
+                                let line_stroke = Stroke {
+                                    color: line_color,
+                                    width: line_size,
+                                    ..Default::default()
+                                };
+                                //Gradients go over all the segments, gaps included, so that they don't restart after a gap
+                                let segment_count = line_points.len().saturating_sub(1);
+                                //Smooth curves are only drawn for the points themselves, selection still uses the points.
+                                //Patterns follow the curve flattened into short segments.
+                                let smooth = plot_settings.smooth && aggregated_points_opt.is_none();
+                                let pattern_runs: Vec<Vec<Point>> = if plot_settings.theme.line_style == LineStyle::Solid {
+                                    Vec::new()
+                                } else {
+                                    runs.iter()
+                                        .map(|(_offset, run)| if smooth && run.len() > 2 {
+                                            crate::math::flatten_catmull_rom(run, 8)
+                                        } else {
+                                            run.to_vec()
+                                        })
+                                        .collect()
+                                };
+                                match plot_settings.theme.line_style {
+                                    LineStyle::Solid => {
+                                        for (offset, run) in runs.iter() {
+                                            if smooth && run.len() > 2 {
+                                                let controls = crate::math::catmull_rom_controls(run);
+                                                if line_gradient_opt.is_some() {
+                                                    for (i, (c1, c2)) in controls.iter().enumerate() {
+                                                        frame.stroke(
+                                                            &Path::new(|builder| {
+                                                                builder.move_to(run[i]);
+                                                                builder.bezier_curve_to(*c1, *c2, run[i + 1]);
+                                                            }),
+                                                            Stroke {
+                                                                color: line_color_at(offset + i, segment_count),
+                                                                ..line_stroke
+                                                            },
+                                                        );
+                                                    }
+                                                } else {
+                                                    frame.stroke(
+                                                        &Path::new(|builder| {
+                                                            builder.move_to(run[0]);
+                                                            for (i, (c1, c2)) in controls.iter().enumerate() {
+                                                                builder.bezier_curve_to(*c1, *c2, run[i + 1]);
+                                                            }
+                                                        }),
+                                                        line_stroke,
+                                                    );
+                                                }
+                                            } else {
+                                                for (i, slice) in run.windows(2).enumerate() {
+                                                    let p1 = slice[0];
+                                                    let p2 = slice[1];
+                                                    let stroke = Stroke {
+                                                        color: line_color_at(offset + i, segment_count),
+                                                        ..line_stroke
+                                                    };
+                                                    frame.stroke(&Path::line(p1, p2), stroke);
+                                                }
+                                            }
+                                        }
+                                    }
+                                    LineStyle::Dashed { dash, gap } => {
+                                        let dashes: Vec<Vec<Point>> = pattern_runs
+                                            .iter()
+                                            .flat_map(|run| crate::math::dash_polyline(run, dash, gap))
+                                            .collect();
+                                        for (i, dash_points) in dashes.iter().enumerate() {
+                                            frame.stroke(
+                                                &Path::new(|builder| {
+                                                    builder.move_to(dash_points[0]);
+                                                    dash_points[1..].iter().for_each(|point| builder.line_to(*point));
+                                                }),
+                                                Stroke {
+                                                    color: line_color_at(i, dashes.len()),
+                                                    ..line_stroke
+                                                },
+                                            );
+                                        }
+                                    }
+                                    LineStyle::Dotted { spacing } => {
+                                        let dots: Vec<Point> = pattern_runs
+                                            .iter()
+                                            .flat_map(|run| crate::math::points_along(run, spacing))
+                                            .collect();
+                                        for (i, dot) in dots.iter().enumerate() {
+                                            frame.fill(&Path::circle(*dot, line_size / 2.0), line_color_at(i, dots.len()));
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    //Draw crossing markers
+                    for crossing in self.crossing_markers(plot_settings.axis, &drawn_points, plot_area) {
                         frame.stroke(
-                            &Path::line(p1, p2),
+                            &Path::circle(crossing, plot_settings.point_size2),
                             Stroke {
                                 color: line_color,
-                                width: line_size,
+                                width: 1.0,
                                 ..Default::default()
                             },
                         );
                     }
 
-                    //Draw points
-                    for (p, _xd, _yd) in vec.iter() {
-                        let selected = selected_point_opt
-                            .map(|(_settings, (selected_point, _xd, _yd))| *selected_point == *p)
-                            .unwrap_or(false);
-                        let size = if selected { selected_point_size } else { point_size };
-                        frame.fill(&Path::circle(*p, size), point_color);
+                    //Draw extremes
+                    if mark_extremes {
+                        let extremes = extreme_points(vec, plot_area)
+                            .into_iter()
+                            .flat_map(|(highest, lowest)| {
+                                vec![(highest, -1.0, VerticalAlignment::Bottom), (lowest, 1.0, VerticalAlignment::Top)]
+                            });
+                        for ((point_index, point), direction, vertical_alignment) in extremes {
+                            frame.stroke(
+                                &Path::circle(point, plot_settings.point_size3),
+                                Stroke {
+                                    color: line_color,
+                                    width: 1.0,
+                                    ..Default::default()
+                                },
+                            );
+                            let (_xd, yd) = &edges[point_index];
+                            frame.fill_text(Text {
+                                content: self.describe_y(yd),
+                                position: Point::new(point.x, point.y + direction * (plot_settings.point_size3 + 2.0)),
+                                color: theme.data_description_color,
+                                size: theme.stats_size,
+                                horizontal_alignment: HorizontalAlignment::Center,
+                                vertical_alignment,
+                                ..Default::default()
+                            });
+                        }
+                    }
+
+                    //Draw points, aggregated plots only show the selected one
+                    let selected_point_index_opt = selected_point_opt
+                        .filter(|(selected_plot_index, _point_index)| *selected_plot_index == plot_index)
+                        .map(|(_plot_index, point_index)| point_index)
+                        .filter(|point_index| plot_area.contains(vec[*point_index]));
+                    if aggregated_points_opt.is_none() {
+                        for point_index in drawn_indices.iter().filter(|point_index| plot_area.contains(vec[**point_index])) {
+                            if selected_point_index_opt != Some(*point_index) {
+                                let color = point_color_at(*point_index);
+                                let color = Color {
+                                    a: color.a * self.fade_in_alpha(plot_index, *point_index),
+                                    ..color
+                                };
+                                frame.fill(&marker_path(plot_settings.theme.marker, vec[*point_index], point_size), color);
+                            }
+                        }
+                    }
+                    //The selected point is drawn last, even if it was decimated away
+                    selected_point_index_opt.iter().for_each(|point_index| {
+                        frame.fill(&marker_path(plot_settings.theme.marker, vec[*point_index], selected_point_size), point_color_at(*point_index));
+                    });
+
+                    //Draw point labels
+                    if self.settings.show_point_labels && aggregated_points_opt.is_none() {
+                        let size = theme.point_label_size;
+                        for point_index in drawn_indices.iter().filter(|point_index| plot_area.contains(vec[**point_index])) {
+                            let point = vec[*point_index];
+                            let (_xd, yd) = &edges[*point_index];
+                            let content = self.describe_y(yd);
+                            let width = text::estimate_width(&content, size);
+                            let area_opt = self.point_label_area(point, Size::new(width, size), point_size + 2.0, &point_label_areas);
+                            if let Some(area) = area_opt {
+                                frame.fill_text(Text {
+                                    content,
+                                    position: area.position(),
+                                    color: theme.point_label_color,
+                                    size,
+                                    horizontal_alignment: HorizontalAlignment::Left,
+                                    vertical_alignment: VerticalAlignment::Top,
+                                    ..Default::default()
+                                });
+                                point_label_areas.push(area);
+                            }
+                        }
                     }
                 }
+
+                //Draw confidence ellipses
+                for ellipse in self.confidence_ellipses.iter() {
+                    let outline = self.ellipse_points(ellipse, margined_area.size());
+                    frame.stroke(
+                        &Path::new(|builder| {
+                            builder.move_to(outline[0]);
+                            outline[1..].iter().for_each(|point| builder.line_to(*point));
+                            builder.close();
+                        }),
+                        Stroke {
+                            color: ellipse.theme.line_color,
+                            width: 2.0,
+                            ..Default::default()
+                        },
+                    );
+                }
             });
+
+            if grid_follows(self.settings.grid_layer, Layer::Data) {
+                self.draw_grid(frame, padded_area, margined_area, cursor_position_opt);
+            }
+
+            //Draw crosshair
+            if let Some(crosshair) = self.settings.crosshair {
+                let inside_cursor_position_opt = cursor_position_opt.filter(|cp| margined_area.contains(*cp));
+                if let Some(cursor_position) = inside_cursor_position_opt {
+                    self.draw_crosshair(frame, margined_area, cursor_position, crosshair);
+                }
+            }
+
+            //Draw the tooltip over the data
+            if let Some((plot_index, point_index)) = selected_point_opt {
+                match self.settings.tooltip_placement {
+                    TooltipPlacement::Header => {}
+                    TooltipPlacement::FollowCursor => {
+                        let content = self.tooltip_content(&points, (plot_index, point_index));
+                        let point = points[plot_index][point_index] + Vector::new(margined_area.x, margined_area.y);
+                        let clearance = self.data[plot_index].0.point_size3 + 6.0;
+                        self.draw_follow_tooltip(frame, margined_area, point, clearance, content);
+                    }
+                    TooltipPlacement::Corner(corner) => {
+                        let content = self.tooltip_content(&points, (plot_index, point_index));
+                        self.draw_corner_tooltip(frame, margined_area, corner, content);
+                    }
+                }
+            }
+
+            //Refine on the next frame if this one was cheap enough
+            if let Some(progressive_detail) = self.settings.progressive_detail {
+                let refine = decimated && started.elapsed() <= progressive_detail.budget;
+                if refine {
+                    self.detail_level.set(self.detail_level.get() + 1);
+                }
+                self.detail_pending.set(refine);
+            }
+
+            //Draw stats
+            if self.settings.show_stats {
+                self.stats_text().iter().for_each(|content| {
+                    let stats_corner = if self.settings.rtl {
+                        self.settings.stats_corner.mirrored()
+                    } else {
+                        self.settings.stats_corner
+                    };
+                    let (position, horizontal_alignment, vertical_alignment) = stats_corner.anchor(padded_area, 5.0);
+                    frame.fill_text(Text {
+                        content: content.clone(),
+                        position,
+                        color: theme.stats_color,
+                        size: theme.stats_size,
+                        horizontal_alignment,
+                        vertical_alignment,
+                        ..Default::default()
+                    });
+                });
+            }
+
+            //Draw watermark
+            if let Some(watermark_text) = self.watermark_text(margined_area) {
+                frame.fill_text(watermark_text);
+            }
+
+            //Draw legend, over everything else
+            if let Some(legend) = self.settings.legend {
+                self.draw_legend(frame, margined_area, legend);
+            }
         });
         vec![result]
     }
@@ -517,15 +3151,106 @@ impl <XV: data::AxisValue, YV: data::AxisValue, XD: data::AxisData<XV>, YD: data
     fn update(
         &mut self,
         event: iced::canvas::Event,
-        _bounds: iced::Rectangle,
-        _cursor: iced::canvas::Cursor,
+        bounds: iced::Rectangle,
+        cursor: iced::canvas::Cursor,
     ) -> (iced::canvas::event::Status, Option<data::Message>) {
+        if let Some(sender) = &self.interaction_sender_opt {
+            let margined_area = self.margined_area(bounds.size());
+            let margined_cursor_position_opt = cursor
+                .position_in(&bounds)
+                .map(|cp| Point::new(cp.x - margined_area.x, cp.y - margined_area.y))
+                .filter(|cp| Rectangle::new(Point::ORIGIN, margined_area.size()).contains(*cp));
+            let values_opt = margined_cursor_position_opt.and_then(|cp| {
+                let x = self.unmap_x(cp.x, margined_area.width)?;
+                let y = self.unmap_y(cp.y, margined_area.height)?;
+                Some((x, y))
+            });
+            let event_opt = match (&event, values_opt) {
+                (iced::canvas::Event::Mouse(iced::mouse::Event::CursorMoved { .. }), Some((x, y))) => {
+                    Some(InteractionEvent::Hover { x, y })
+                }
+                (iced::canvas::Event::Mouse(iced::mouse::Event::ButtonPressed(iced::mouse::Button::Left)), Some((x, y))) => {
+                    Some(InteractionEvent::Click { x, y })
+                }
+                (iced::canvas::Event::Mouse(_), None) if self.cursor_inside => Some(InteractionEvent::Leave),
+                _ => None,
+            };
+            if let iced::canvas::Event::Mouse(_) = event {
+                self.cursor_inside = margined_cursor_position_opt.is_some();
+            }
+            if let Some(event) = event_opt {
+                //The receiver may be gone, losing the event is fine then
+                let _ = sender.send(event);
+            }
+        }
+        if self.settings.reset_view_on_double_click {
+            if let iced::canvas::Event::Mouse(iced::mouse::Event::ButtonPressed(iced::mouse::Button::Left)) = event {
+                let margined_area = self.margined_area(bounds.size());
+                if cursor.position_in(&bounds).filter(|cp| margined_area.contains(*cp)).is_some() {
+                    let now = Instant::now();
+                    let double_click = self
+                        .last_click_opt
+                        .map_or(false, |last_click| now.duration_since(last_click) <= DOUBLE_CLICK_INTERVAL);
+                    if double_click {
+                        self.last_click_opt = None;
+                        self.reset_view();
+                        return (iced::canvas::event::Status::Captured, None);
+                    }
+                    self.last_click_opt = Some(now);
+                }
+            }
+        }
+        if self.settings.pan {
+            match event {
+                iced::canvas::Event::Mouse(iced::mouse::Event::ButtonPressed(iced::mouse::Button::Left)) => {
+                    let margined_area = self.margined_area(bounds.size());
+                    let anchor_opt = cursor.position_in(&bounds).filter(|cp| margined_area.contains(*cp));
+                    if let Some(anchor) = anchor_opt {
+                        //Dragging takes over from a running animation
+                        self.animation_opt = None;
+                        self.pan_state_opt = Some(PanState {
+                            anchor,
+                            view_state: self.view_state(),
+                        });
+                        return (iced::canvas::event::Status::Captured, None);
+                    }
+                }
+                iced::canvas::Event::Mouse(iced::mouse::Event::ButtonReleased(iced::mouse::Button::Left)) => {
+                    if self.pan_state_opt.take().is_some() {
+                        return (iced::canvas::event::Status::Captured, None);
+                    }
+                }
+                _ => {}
+            }
+        }
         match event {
             iced::canvas::Event::Mouse(iced::mouse::Event::CursorMoved { .. }) => {
+                //Positions outside of the canvas still move the window while dragging
+                if let Some(cursor_position) = cursor.position() {
+                    let cursor_position = Point::new(cursor_position.x - bounds.x, cursor_position.y - bounds.y);
+                    self.pan_to(cursor_position, bounds.size());
+                }
                 self.cache.clear();
+                //Reported only when the hovered point changes
+                let margined_area = self.margined_area(bounds.size());
+                let hovered_point_opt = cursor.position_in(&bounds).and_then(|cp| {
+                    let margined_cursor_position = Point::new(cp.x - margined_area.x, cp.y - margined_area.y);
+                    let points = self.points(margined_area.size());
+                    self.find_selected_point(&points, margined_cursor_position, margined_area.size())
+                });
+                if hovered_point_opt != self.hovered_point_opt {
+                    self.hovered_point_opt = hovered_point_opt;
+                    return (iced::canvas::event::Status::Captured, Some(data::Message::HoverChanged(hovered_point_opt)));
+                }
                 (iced::canvas::event::Status::Captured, None)
             }
-            _ => (iced::canvas::event::Status::Ignored, None),
+            _ => {
+                //Let the next frame draw with more detail
+                if self.detail_pending.get() {
+                    self.cache.clear();
+                }
+                (iced::canvas::event::Status::Ignored, None)
+            }
         }
     }
 
@@ -534,11 +3259,7 @@ impl <XV: data::AxisValue, YV: data::AxisValue, XD: data::AxisData<XV>, YD: data
         bounds: iced::Rectangle,
         cursor: iced::canvas::Cursor,
     ) -> iced::mouse::Interaction {
-        let size = bounds.size();
-
-        let full_area = Rectangle::new(Point::ORIGIN, size);
-        let padded_area = self.settings.padding.transform(full_area);
-        let margined_area = self.settings.margin.transform(padded_area);
+        let margined_area = self.margined_area(bounds.size());
 
         let cursor_position_opt = cursor.position_in(&bounds);
         let margined_cursor_position_opt = cursor_position_opt
@@ -547,15 +3268,8 @@ impl <XV: data::AxisValue, YV: data::AxisValue, XD: data::AxisData<XV>, YD: data
         margined_cursor_position_opt
             .and_then(|cursor_position| {
                 let points = self.points(margined_area.size());
-                let hovered = points.iter().any(|(_settings, vec)| {
-                    vec.windows(2).any(|slice| {
-                        let (p1, _xd1, _yd1) = &slice[0];
-                        let (p2, _xd2, _yd2) = &slice[1];
-                        crate::math::point_to_interval_distance(cursor_position, *p1, *p2) <= 6.0
-                            || cursor_position.distance(*p1) <= 14.0
-                            || cursor_position.distance(*p2) <= 14.0
-                    })
-                });
+                let hovered = self.find_selected_point(&points, cursor_position, margined_area.size()).is_some()
+                    || self.find_selected_plot(&points, cursor_position).is_some();
                 hovered.then_some(iced::mouse::Interaction::Pointer)
             })
             .unwrap_or(iced::mouse::Interaction::default())
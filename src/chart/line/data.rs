@@ -1,27 +1,55 @@
 use iced::{Point, Rectangle, Size};
 use iced::Color;
-use std::{cmp::Ordering, hash::Hash};
+use iced::{HorizontalAlignment, VerticalAlignment};
+use std::{cmp::Ordering, hash::Hash, time::Duration as StdDuration};
 
 #[cfg(feature = "chrono")]
 use chrono::{Date, DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, offset::TimeZone};
 
+#[cfg(feature = "decimal")]
+use rust_decimal::{Decimal, prelude::{FromPrimitive, ToPrimitive}};
+
 #[derive(Debug, Clone)]
 pub struct ThemeSettings {
     pub background_color: Color,
     pub padded_background_color: Color,
     pub margined_background_color: Option<Color>,
+    //Applies to the padded and margined backgrounds, 0.0 keeps them sharp.
+    //Only the backgrounds are rounded: the iced 0.3 canvas can't clip, so gridlines, tick marks and plots
+    //still run square into the corners. Keep the radius smaller than the margin to hide that.
+    pub background_corner_radius: f32,
     pub title_color: Color,
     pub title_size: f32,
+    pub subtitle_size: f32, //The subtitle has the color of the title
+    pub axis_title_size: f32, //Axis titles have the color of the labels of their axis
     pub data_description_color: Color,
     pub data_description_size: f32,
+    pub tooltip_max_width: Option<f32>, //Longer point descriptions are wrapped
+    pub stats_color: Color,
+    pub stats_size: f32,
+    pub point_label_color: Color,
+    pub point_label_size: f32,
     pub x_label_text_color: Color,
     pub x_label_text_size: f32,
     pub x_label_line_color: Color,
     pub x_label_line_width: f32,
+    pub x_label_alignment: LabelAlignment,
     pub y_label_text_color: Color,
     pub y_label_text_size: f32,
     pub y_label_line_color: Color,
     pub y_label_line_width: f32,
+    pub y_label_alignment: LabelAlignment,
+    pub mirror_ticks: bool, //Tick marks on the right and the top margins too
+    //Gridlines across the margined area and the tick marks in the margins can be turned off separately,
+    //the labels are drawn either way
+    pub x_gridlines: bool,
+    pub y_gridlines: bool,
+    pub x_ticks: bool,
+    pub y_ticks: bool,
+    //Gridlines and tick marks are snapped to the pixel grid so they look crisp even with antialiasing,
+    //data lines are always drawn smooth
+    pub crisp_gridlines: bool,
+    pub grid_highlight_color: Color, //Used by `Settings::highlight_nearest_gridline`
 }
 
 impl Default for ThemeSettings {
@@ -30,10 +58,18 @@ impl Default for ThemeSettings {
             background_color: Color::from_rgb8(211, 211, 211),
             padded_background_color: Color::WHITE,
             margined_background_color: Some(Color::from_rgb8(241, 241, 241)),
+            background_corner_radius: 0.0,
             title_color: Color::BLACK,
             title_size: 32.0,
+            subtitle_size: 16.0,
+            axis_title_size: 14.0,
             data_description_color: Color::BLACK,
             data_description_size: 16.0,
+            tooltip_max_width: None,
+            stats_color: Color::BLACK,
+            stats_size: 12.0,
+            point_label_color: Color::BLACK,
+            point_label_size: 10.0,
             x_label_text_color: Color::BLACK,
             x_label_text_size: 12.0,
             x_label_line_width: 3.0,
@@ -41,6 +77,10 @@ impl Default for ThemeSettings {
                 a: 0.8,
                 ..Color::BLACK
             },
+            x_label_alignment: LabelAlignment {
+                horizontal: HorizontalAlignment::Center,
+                vertical: VerticalAlignment::Top,
+            },
             y_label_text_color: Color::BLACK,
             y_label_text_size: 12.0,
             y_label_line_width: 3.0,
@@ -48,18 +88,373 @@ impl Default for ThemeSettings {
                 a: 0.8,
                 ..Color::BLACK
             },
+            y_label_alignment: LabelAlignment {
+                horizontal: HorizontalAlignment::Right,
+                vertical: VerticalAlignment::Center,
+            },
+            mirror_ticks: true,
+            x_gridlines: true,
+            y_gridlines: true,
+            x_ticks: true,
+            y_ticks: true,
+            crisp_gridlines: false,
+            grid_highlight_color: Color::BLACK,
         }
     }
 }
 
+impl ThemeSettings {
+    //Light text on dark gray backgrounds, the counterpart of the default light theme
+    pub fn dark() -> Self {
+        let text_color = Color::from_rgb8(220, 220, 220);
+        let grid_color = Color {
+            a: 0.3,
+            ..text_color
+        };
+        Self {
+            background_color: Color::from_rgb8(30, 30, 30),
+            padded_background_color: Color::from_rgb8(40, 40, 40),
+            margined_background_color: Some(Color::from_rgb8(50, 50, 50)),
+            title_color: text_color,
+            data_description_color: text_color,
+            stats_color: text_color,
+            point_label_color: text_color,
+            x_label_text_color: text_color,
+            x_label_line_color: grid_color,
+            y_label_text_color: text_color,
+            y_label_line_color: grid_color,
+            grid_highlight_color: Color::WHITE,
+            ..Default::default()
+        }
+    }
+}
+
+//Builds `ThemeSettings` starting from the defaults, with setters changing related fields together
+#[derive(Debug, Clone, Default)]
+pub struct ThemeSettingsBuilder {
+    theme: ThemeSettings,
+}
+
+impl ThemeSettingsBuilder {
+    pub fn new() -> Self {
+        Self {
+            theme: Default::default(),
+        }
+    }
+
+    pub fn from_theme(theme: ThemeSettings) -> Self {
+        Self { theme }
+    }
+
+    pub fn build(self) -> ThemeSettings {
+        self.theme
+    }
+
+    //Color of the gridlines of both axes
+    pub fn grid_color(mut self, color: Color) -> Self {
+        self.theme.x_label_line_color = color;
+        self.theme.y_label_line_color = color;
+        self
+    }
+
+    //Width of the gridlines of both axes
+    pub fn grid_width(mut self, width: f32) -> Self {
+        self.theme.x_label_line_width = width;
+        self.theme.y_label_line_width = width;
+        self
+    }
+
+    //Color of every text: title, axis labels, point description and stats
+    pub fn text_color(mut self, color: Color) -> Self {
+        self.theme.title_color = color;
+        self.theme.data_description_color = color;
+        self.theme.stats_color = color;
+        self.theme.point_label_color = color;
+        self.theme.x_label_text_color = color;
+        self.theme.y_label_text_color = color;
+        self
+    }
+
+    //Text size of the labels of both axes
+    pub fn label_text_size(mut self, size: f32) -> Self {
+        self.theme.x_label_text_size = size;
+        self.theme.y_label_text_size = size;
+        self
+    }
+}
+
+//Alignment of the axis label text.
+//The alignment across the axis also picks the anchor inside the padding:
+//e.g. `Left` for y labels anchors them at the left edge of the chart, `Right` next to the plot.
+#[derive(Debug, Clone, Copy)]
+pub struct LabelAlignment {
+    pub horizontal: HorizontalAlignment,
+    pub vertical: VerticalAlignment,
+}
+
 #[derive(Debug, Clone)]
 pub struct Settings {
     pub theme: ThemeSettings,
     pub title: Option<String>,
+    pub subtitle: Option<String>, //Under the title, or in its place when there is no title
+    //Along the outer edges of the padding: the x title at the bottom, the y title rotated on the side of the y labels.
+    //The padding isn't enlarged for them, it needs room for the labels and the titles next to each other.
+    pub x_axis_title: Option<String>,
+    pub y_axis_title: Option<String>,
+    pub title_alignment: HorizontalAlignment, //Within the padded area, mirrored in right to left charts, applies to the subtitle too
     pub padding: QuadDistance,
     pub margin: QuadDistance,
     pub min_x_label_distance: DistanceValue,
     pub min_y_label_distance: DistanceValue,
+    //About this many labels (gridlines) along the axis, the bounds included, whatever the size of the chart.
+    //Replaces the matching `min_*_label_distance` when set.
+    pub x_label_count: Option<usize>,
+    pub y_label_count: Option<usize>,
+    pub show_stats: bool,
+    pub stats_plot_index: usize, //The plot whose values are summarized
+    pub stats_corner: Corner,
+    pub readout_mode: ReadoutMode,
+    pub fill_blend: BlendMode, //Used for every translucent fill drawn over the data
+    pub progressive_detail: Option<ProgressiveDetail>,
+    pub x_scale: ScaleKind,
+    pub y_scale: ScaleKind,
+    pub selection_emphasis: SelectionEmphasis,
+    pub point_selection: PointSelection,
+    pub rtl: bool, //Right to left layout: x grows leftwards, y labels are on the right
+    pub pixel_aggregate: bool, //Points sharing a pixel column are drawn as a min/max bar
+    pub legend_show_counts: bool, //Legend entries also show the number of points of their plot
+    //Target spacing of the minor gridlines drawn between the labeled ones, no minor gridlines if `None`
+    pub minor_grid_density: Option<DistanceValue>,
+    pub coincident_points: CoincidentPolicy,
+    pub thousands_separator: Option<char>, //Put into numeric labels and descriptions, e.g. `Some(',')` gives "1,234,567"
+    pub watermark: Option<Watermark>, //Text only, images go in a separate widget under the canvas
+    //Rings and value tags on the highest and the lowest point of every plot.
+    //`None` enables them only for charts with a single plot.
+    pub mark_extremes: Option<bool>,
+    pub show_point_labels: bool, //The y value above every drawn point
+    pub point_label_overflow: LabelOverflow,
+    //The x axis line, tick marks and labels go to the zero line when it is inside of the y range
+    pub x_axis_at_zero: bool,
+    pub highlight_nearest_gridline: bool, //The x and y gridlines closest to the cursor are drawn thicker
+    //Points added with `Chart::push_point` reach full opacity after this many `Chart::tick` calls, 0 shows them at once
+    pub fade_in_ticks: u32,
+    //`Chart::set_view_state` eases the bounds to the new ones over this long, advanced by `Chart::tick`. `None` jumps at once.
+    pub bounds_animation: Option<StdDuration>,
+    //Safety cap: above this many points in total every plot is decimated, keeping its share of the points
+    pub max_total_points: Option<usize>,
+    pub legend: Option<LegendSettings>, //Lists the plots with a name
+    pub show_full_resolution_ghost: bool, //Decimated plots also get all of their points as a faint line behind
+    pub tooltip_placement: TooltipPlacement,
+    //Color of the y = x line, drawn where the x and y ranges overlap (only for values with a zero)
+    pub identity_line: Option<Color>,
+    pub streaming_bounds: StreamingBounds, //How `Chart::push_point` changes the bounds
+    pub pan: bool, //Dragging with the left button moves the visible window
+    pub reset_view_on_double_click: bool, //Double-clicking the margined area calls `Chart::reset_view`
+    pub crosshair: Option<CrosshairSettings>, //Guide lines through the cursor with its values at the axes
+    pub grid_layer: GridLayer, //Where the gridlines and the labels go between the backgrounds and the data
+    //Degrees the x labels are rotated by around their tick (e.g. -45.0 for long dates), positive is clockwise.
+    //Rotated labels hang from the tick, the bottom padding has to be made big enough for them.
+    pub x_label_rotation: f32,
+    pub number_format: NumberFormat, //Applied to numeric labels before `thousands_separator`
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberFormat {
+    Plain, //As `AxisData::display_value` writes them
+    //Numbers with a magnitude of at least `threshold` or below `1 / threshold` are written like `1.20e9`
+    Scientific { threshold: f64, precision: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridLayer {
+    BelowMarginedBackground, //The margined background hides the gridlines, only the tick marks stay
+    BelowData,
+    AboveData,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrosshairSettings {
+    pub color: Color,
+    pub width: f32,
+    pub dashed: bool,
+}
+
+impl Default for CrosshairSettings {
+    fn default() -> Self {
+        Self {
+            color: Color {
+                a: 0.6,
+                ..Color::BLACK
+            },
+            width: 1.0,
+            dashed: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamingBounds {
+    Fixed, //The bounds never change, points outside of them are clipped
+    //The bounds grow to include every new point, plus `headroom` of the range (e.g. 0.1) beyond it
+    Expand { headroom: f32 },
+    //The x window keeps its width and follows the newest point, the points left behind are dropped.
+    //The y bounds grow like with `Expand` without headroom.
+    Slide,
+}
+
+//Where the description of the selected point goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TooltipPlacement {
+    Header,       //In the padding above the chart, opposite to the title
+    //In a box next to the selected point, on the side where it doesn't cover the point,
+    //with a leader line to the point. The box stays inside of the canvas.
+    FollowCursor,
+    Corner(Corner), //In a box in the corner of the margined area
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LegendSettings {
+    pub corner: Corner, //Corner of the margined area, mirrored for right to left charts
+    pub background_color: Color,
+    pub text_color: Color,
+    pub text_size: f32,
+}
+
+impl Default for LegendSettings {
+    fn default() -> Self {
+        Self {
+            corner: Corner::TopRight,
+            background_color: Color {
+                a: 0.8,
+                ..Color::WHITE
+            },
+            text_color: Color::BLACK,
+            text_size: 12.0,
+        }
+    }
+}
+
+//What happens to a point label overlapping one drawn before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelOverflow {
+    Overlap, //Drawn anyway
+    Hide,
+    Nudge,   //Moved below the point, hidden if it overlaps there too
+}
+
+//Which point is selected when points of several plots are about as close to the cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoincidentPolicy {
+    FirstSeries,   //The point of the plot with the lowest index
+    ClosestStable, //The closest point, nearly equal distances are won by the lowest plot index
+    ShowAll,       //Like `ClosestStable`, but the tooltip describes every point at the same place
+}
+
+//How the cursor selects a point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointSelection {
+    Radius, //The closest point within `PlotSettings::point_selection_distance`
+    //Anywhere inside of the margined area: the point closest in x of every plot is described,
+    //the closest of them is selected
+    NearestX,
+}
+
+//How the selected plot stands out, on top of using `PlotSettings::line_size2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionEmphasis {
+    Thicken,   //Only the thicker line
+    Recolor,   //Drawn with `PlotThemeSettings::selected_line_color`
+    DimOthers, //The other plots get translucent
+    Glow,      //A wide translucent halo under the line
+}
+
+//How values are spread along an axis.
+//Non-linear scales are applied around the origin of the axis: zero for numeric values,
+//otherwise the axis minimum (see `AxisValue::zero`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScaleKind {
+    Linear,
+    //Linear inside of [-linthresh; linthresh] and logarithmic beyond it, works for data crossing zero
+    SymLog { linthresh: f32 },
+    //Logarithmic, offsets below `floor` (including non-positive ones) are drawn at `floor`.
+    //On time axes without a zero the offsets are the elapsed milliseconds (days for dates) since the minimum,
+    //so `floor: 1.0` gives ticks at 1ms, 10ms, 100ms, 1s and so on.
+    Log { floor: f32 },
+}
+
+impl ScaleKind {
+    //Transforms an offset from the origin
+    pub fn transform(&self, value: f32) -> f32 {
+        match *self {
+            ScaleKind::Linear => value,
+            ScaleKind::SymLog { linthresh } => {
+                if value.abs() <= linthresh {
+                    value
+                } else {
+                    value.signum() * linthresh * (1.0 + (value.abs() / linthresh).log10())
+                }
+            }
+            ScaleKind::Log { floor } => value.max(floor).max(f32::MIN_POSITIVE).log10(),
+        }
+    }
+
+    //Inverse of `transform`, offsets which `Log` draws at its floor come back as the floor
+    pub fn inverse(&self, value: f32) -> f32 {
+        match *self {
+            ScaleKind::Linear => value,
+            ScaleKind::SymLog { linthresh } => {
+                if value.abs() <= linthresh {
+                    value
+                } else {
+                    value.signum() * linthresh * 10f32.powf(value.abs() / linthresh - 1.0)
+                }
+            }
+            ScaleKind::Log { .. } => 10f32.powf(value),
+        }
+    }
+
+    //Offsets from the origin inside of [lo; hi] where the ticks of this scale go, sorted.
+    //Linear scales are ticked evenly instead, so they have none.
+    pub fn tick_offsets(&self, lo: f32, hi: f32) -> Vec<f32> {
+        let mut result = Vec::new();
+        match *self {
+            ScaleKind::Linear => {}
+            ScaleKind::SymLog { linthresh } => {
+                if linthresh <= 0.0 {
+                    return result;
+                }
+                //Zero and the threshold from the linear region, decades from the logarithmic one
+                result.push(0.0);
+                let mut magnitude = linthresh;
+                while magnitude <= lo.abs().max(hi.abs()) {
+                    result.push(magnitude);
+                    result.push(-magnitude);
+                    magnitude *= 10.0;
+                }
+            }
+            ScaleKind::Log { floor } => {
+                //Every decade from the first one not below the floor
+                let lowest = lo.max(floor).max(f32::MIN_POSITIVE);
+                let mut magnitude = 10f32.powf(lowest.log10().ceil());
+                while magnitude <= hi {
+                    result.push(magnitude);
+                    magnitude *= 10.0;
+                }
+            }
+        }
+        result.retain(|offset| lo <= *offset && *offset <= hi);
+        result.sort_by(|offset1, offset2| offset1.total_cmp(offset2));
+        result
+    }
+}
+
+//Draws big plots decimated at first and adds detail on the following frames,
+//as long as a frame takes less time than `budget`.
+//The following frames are drawn on `Chart::tick`, changing the data or the bounds starts over from `initial_points`.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressiveDetail {
+    pub initial_points: usize, //Points per plot drawn on the first frame, doubled on every refinement
+    pub budget: StdDuration,
 }
 
 impl Default for Settings {
@@ -67,10 +462,62 @@ impl Default for Settings {
         Self {
             theme: Default::default(),
             title: None,
+            subtitle: None,
+            x_axis_title: None,
+            y_axis_title: None,
+            title_alignment: HorizontalAlignment::Left,
             padding: QuadDistance::from1(DistanceValue::Fixed(60.0)),
             margin: QuadDistance::from1(DistanceValue::Fixed(20.0)),
             min_x_label_distance: DistanceValue::Fixed(100.0),
             min_y_label_distance: DistanceValue::Fixed(50.0),
+            x_label_count: None,
+            y_label_count: None,
+            show_stats: false,
+            stats_plot_index: 0,
+            stats_corner: Corner::TopLeft,
+            readout_mode: ReadoutMode::Nearest,
+            fill_blend: BlendMode::Normal,
+            progressive_detail: None,
+            x_scale: ScaleKind::Linear,
+            y_scale: ScaleKind::Linear,
+            selection_emphasis: SelectionEmphasis::Thicken,
+            point_selection: PointSelection::Radius,
+            rtl: false,
+            pixel_aggregate: false,
+            legend_show_counts: false,
+            minor_grid_density: None,
+            coincident_points: CoincidentPolicy::ClosestStable,
+            thousands_separator: None,
+            watermark: None,
+            mark_extremes: None,
+            show_point_labels: false,
+            point_label_overflow: LabelOverflow::Hide,
+            x_axis_at_zero: false,
+            highlight_nearest_gridline: false,
+            fade_in_ticks: 0,
+            bounds_animation: None,
+            max_total_points: None,
+            legend: None,
+            show_full_resolution_ghost: false,
+            tooltip_placement: TooltipPlacement::Header,
+            identity_line: None,
+            streaming_bounds: StreamingBounds::Fixed,
+            pan: false,
+            reset_view_on_double_click: false,
+            crosshair: None,
+            grid_layer: GridLayer::BelowData,
+            x_label_rotation: 0.0,
+            number_format: NumberFormat::Plain,
+        }
+    }
+}
+
+impl Settings {
+    //The defaults with `ThemeSettings::dark`
+    pub fn dark() -> Self {
+        Self {
+            theme: ThemeSettings::dark(),
+            ..Default::default()
         }
     }
 }
@@ -79,6 +526,11 @@ impl Default for Settings {
 pub struct PlotThemeSettings {
     pub line_color: Color,
     pub point_color: Color,
+    pub selected_line_color: Option<Color>, //Used by `SelectionEmphasis::Recolor`, falls back to `line_color`
+    pub line_style: LineStyle,
+    pub marker: Marker,
+    pub fill_color: Option<Color>, //Fills the area between the line and the zero line (or the closest y bound)
+    pub line_gradient: Option<(Color, Color)>, //Segments go from the first color to the second one instead of `line_color`
 }
 
 impl Default for PlotThemeSettings {
@@ -86,26 +538,92 @@ impl Default for PlotThemeSettings {
         Self {
             line_color: Color::from_rgb8(200, 0, 0),
             point_color: Color::from_rgb8(200, 0, 0),
+            selected_line_color: None,
+            line_style: LineStyle::Solid,
+            marker: Marker::Circle,
+            fill_color: None,
+            line_gradient: None,
+        }
+    }
+}
+
+//Shape of the points, drawn as large as the circle of the same point size would be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Marker {
+    Circle,
+    Square,
+    Triangle,
+    Diamond,
+    Cross,
+}
+
+//Lengths are in pixels, patterns continue over the points of the line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineStyle {
+    Solid,
+    Dashed { dash: f32, gap: f32 },
+    Dotted { spacing: f32 }, //Dots as wide as the line
+}
+
+impl Hash for LineStyle {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match *self {
+            LineStyle::Solid => state.write_u8(0),
+            LineStyle::Dashed { dash, gap } => {
+                state.write_u8(1);
+                state.write_u32(dash.to_bits());
+                state.write_u32(gap.to_bits());
+            }
+            LineStyle::Dotted { spacing } => {
+                state.write_u8(2);
+                state.write_u32(spacing.to_bits());
+            }
         }
     }
 }
 
+fn hash_color<H: std::hash::Hasher>(color: &Color, state: &mut H) {
+    state.write_u32(color.r.to_bits());
+    state.write_u32(color.g.to_bits());
+    state.write_u32(color.b.to_bits());
+    state.write_u32(color.a.to_bits());
+}
+
 impl Hash for PlotThemeSettings {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        state.write_u32(self.line_color.r.to_bits());
-        state.write_u32(self.line_color.g.to_bits());
-        state.write_u32(self.line_color.b.to_bits());
-        state.write_u32(self.line_color.a.to_bits());
-        state.write_u32(self.point_color.r.to_bits());
-        state.write_u32(self.point_color.g.to_bits());
-        state.write_u32(self.point_color.b.to_bits());
-        state.write_u32(self.point_color.a.to_bits());
+        hash_color(&self.line_color, state);
+        hash_color(&self.point_color, state);
+        match &self.selected_line_color {
+            Some(color) => {
+                state.write_u8(1);
+                hash_color(color, state);
+            }
+            None => state.write_u8(0),
+        }
+        self.line_style.hash(state);
+        self.marker.hash(state);
+        match &self.fill_color {
+            Some(color) => {
+                state.write_u8(1);
+                hash_color(color, state);
+            }
+            None => state.write_u8(0),
+        }
+        match &self.line_gradient {
+            Some((from, to)) => {
+                state.write_u8(1);
+                hash_color(from, state);
+                hash_color(to, state);
+            }
+            None => state.write_u8(0),
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct PlotSettings {
     pub theme: PlotThemeSettings,
+    pub name: Option<String>, //Shown in the legend
     pub line_selection_distance: f32,
     pub point_selection_distance: f32,
     pub line_size1: f32,  //Line is not selected
@@ -113,12 +631,17 @@ pub struct PlotSettings {
     pub point_size1: f32, //Point is not selected
     pub point_size2: f32, //Point is selected inderectly (through a selected line)
     pub point_size3: f32, //Point is selected directly
+    pub jitter: f32, //Points are moved horizontally by up to this many pixels (the same way on every frame)
+    pub smooth: bool, //The line is a Catmull-Rom curve through the points instead of straight segments
+    pub point_colors: Option<Vec<Color>>, //Overrides `theme.point_color` by point index, points past the end keep it
+    pub axis: YAxis,
 }
 
 impl Default for PlotSettings {
     fn default() -> Self {
         Self {
             theme: Default::default(),
+            name: None,
             line_selection_distance: 4.0,
             point_selection_distance: 10.0,
             line_size1: 2.0,
@@ -126,6 +649,10 @@ impl Default for PlotSettings {
             point_size1: 5.0,
             point_size2: 7.0,
             point_size3: 10.0,
+            jitter: 0.0,
+            smooth: false,
+            point_colors: None,
+            axis: YAxis::Primary,
         }
     }
 }
@@ -133,25 +660,53 @@ impl Default for PlotSettings {
 impl PartialEq for PlotSettings {
     fn eq(&self, other: &Self) -> bool {
         self.theme == other.theme
+            && self.name == other.name
             && self.line_size1 == other.line_size1
             && self.line_size2 == other.line_size2
             && self.point_size1 == other.point_size1
             && self.point_size2 == other.point_size2
             && self.point_size3 == other.point_size3
+            && self.jitter == other.jitter
+            && self.smooth == other.smooth
+            && self.point_colors == other.point_colors
+            && self.axis == other.axis
     }
 }
 
+//Needed to key maps by the settings, see `ChartBuilder::data_map`
+impl Eq for PlotSettings {}
+
 impl Hash for PlotSettings {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         PlotThemeSettings::hash(&self.theme, state);
+        self.name.hash(state);
         state.write_u32(self.line_size1.to_bits());
         state.write_u32(self.line_size2.to_bits());
         state.write_u32(self.point_size1.to_bits());
         state.write_u32(self.point_size2.to_bits());
         state.write_u32(self.point_size3.to_bits());
+        state.write_u32(self.jitter.to_bits());
+        self.smooth.hash(state);
+        match &self.point_colors {
+            Some(point_colors) => {
+                state.write_u8(1);
+                state.write_usize(point_colors.len());
+                point_colors.iter().for_each(|color| hash_color(color, state));
+            }
+            None => state.write_u8(0),
+        }
+        self.axis.hash(state);
     }
 }
 
+//The y axis a plot is mapped to. The secondary one has its own range and labels on the right,
+//plots fall back to the primary range when the chart has no secondary one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YAxis {
+    Primary,
+    Secondary,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum DistanceValue {
     Fixed(f32),
@@ -239,14 +794,167 @@ impl QuadDistance {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Message {}
+//How the y value of a plot is read at an arbitrary x (see `Chart::readout`) by the crosshair and the nearest x tooltip.
+//Every mode reads nothing when x is outside of the plot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadoutMode {
+    Nearest,     //The y value of the point with the closest x
+    Interpolate, //Linear interpolation between the two neighbouring points
+    None,        //No readout at all
+}
+
+//How overlapping fills are combined.
+//The iced canvas only supports regular alpha compositing, so the other modes are approximated
+//by adjusting the fill color itself instead of blending with what is already drawn.
+//This means they are not real blend modes: `Multiply` darkens every fill on its own (it multiplies the color by itself),
+//and overlapping fills are still alpha composited, never multiplied with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    Normal,
+    Additive, //Lower opacity, so overlapping fills add up instead of hiding each other
+    Multiply, //Darker color, so overlapping fills get darker
+}
+
+impl BlendMode {
+    pub fn apply(&self, color: Color) -> Color {
+        match self {
+            BlendMode::Normal => color,
+            BlendMode::Additive => Color {
+                a: color.a / 2.0,
+                ..color
+            },
+            BlendMode::Multiply => Color {
+                r: color.r * color.r,
+                g: color.g * color.g,
+                b: color.b * color.b,
+                ..color
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    //The corner on the other horizontal side
+    pub fn mirrored(&self) -> Self {
+        match self {
+            Corner::TopLeft => Corner::TopRight,
+            Corner::TopRight => Corner::TopLeft,
+            Corner::BottomLeft => Corner::BottomRight,
+            Corner::BottomRight => Corner::BottomLeft,
+        }
+    }
+
+    //Returns the point `offset` away from the corner of `area`,
+    //and the text alignment which keeps the content inside of `area`
+    pub fn anchor(&self, area: Rectangle, offset: f32) -> (Point, HorizontalAlignment, VerticalAlignment) {
+        let left = area.x + offset;
+        let right = area.x + area.width - offset;
+        let top = area.y + offset;
+        let bottom = area.y + area.height - offset;
+        match self {
+            Corner::TopLeft => (Point::new(left, top), HorizontalAlignment::Left, VerticalAlignment::Top),
+            Corner::TopRight => (Point::new(right, top), HorizontalAlignment::Right, VerticalAlignment::Top),
+            Corner::BottomLeft => (Point::new(left, bottom), HorizontalAlignment::Left, VerticalAlignment::Bottom),
+            Corner::BottomRight => (Point::new(right, bottom), HorizontalAlignment::Right, VerticalAlignment::Bottom),
+        }
+    }
+}
+
+//Text drawn over the data in a corner of the margined area.
+//The iced 0.3 canvas has no image primitive, so only text is supported:
+//for an image (e.g. a logo) put an `Image` widget under the canvas, e.g. in a `Column` with it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watermark {
+    pub text: String,
+    pub corner: Corner,
+    pub color: Color,
+    pub size: f32,
+    pub opacity: f32, //Multiplies the alpha of `color`
+}
+
+impl Default for Watermark {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            corner: Corner::BottomRight,
+            color: Color::BLACK,
+            size: 24.0,
+            opacity: 0.15,
+        }
+    }
+}
+
+//Sent to the sender given to `ChartBuilder::interaction_events`, with the values under the cursor
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractionEvent<XV, YV> {
+    Hover { x: XV, y: YV },
+    Click { x: XV, y: YV },
+    Leave, //The cursor left the margined area
+}
+
+//The visible window of a chart, see `Chart::view_state`.
+//It is only the bounds: the selection always follows the cursor (nothing can be pinned)
+//and plots can't be hidden, so there is no other view state to save.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewState<XV, YV> {
+    pub min_x_value: XV,
+    pub max_x_value: XV,
+    pub min_y_value: YV,
+    pub max_y_value: YV,
+}
+
+//A problem with the data found by `ChartBuilder::validate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationWarning {
+    EmptyPlot { plot_index: usize },
+    NonFiniteValue { plot_index: usize, point_index: usize },
+    UnsortedX { plot_index: usize, point_index: usize }, //The point has a smaller x than the previous one
+    DuplicateX { plot_index: usize, point_index: usize }, //The point has the same x as the previous one
+    OutOfBounds { plot_index: usize, point_index: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    //The point under the cursor changed, as (plot index, point index), `None` when no point is hovered
+    HoverChanged(Option<(usize, usize)>),
+}
 
 pub trait AxisValue: Clone {
     fn compare_value(&self, other: &Self) -> Ordering;
-    //self <= other
+    //Absolute distance, the arguments may go in any order
     fn distance_to(&self, other: &Self) -> f32;
     fn add(&self, value: f32) -> Option<Self>;
+    //Natural steps between ticks (in the units of `distance_to`), sorted ascending.
+    //Ticks snap to the smallest one not denser than the calculated step, an empty list keeps the calculated step.
+    fn preferred_steps() -> Vec<f32> {
+        Vec::new()
+    }
+    //False for values which can't be plotted, like NaN.
+    //Points with such a value are gaps: they aren't drawn or selected and the line breaks there,
+    //e.g. push `(x, f32::NAN)` to leave out the data at `x`.
+    fn is_finite(&self) -> bool {
+        true
+    }
+    //The zero of the value type, if it has one. Used as the origin of non-linear scales.
+    fn zero() -> Option<Self> {
+        None
+    }
+    //Like `distance_to`, but negative when other < self
+    fn signed_distance_to(&self, other: &Self) -> f32 {
+        match self.compare_value(other) {
+            Ordering::Greater => -self.distance_to(other),
+            _ => self.distance_to(other),
+        }
+    }
     //self <= other, 0 < min_distance <= optimal_distance
     fn get_values_in_between(
         &self,
@@ -275,26 +983,41 @@ macro_rules! integer_axis_value_impl {
                     self.cmp(other)
                 }
 
+                //The difference is taken before the conversion, so close values keep distinct distances
+                //even far from zero (e.g. u64 nanosecond timestamps).
+                //128 bit ranges can be too far for an f32, they end at `f32::MAX` instead of infinity.
                 fn distance_to(&self, other: &Self) -> f32 {
-                    assert!(*self <= *other);
-                    (*other - *self) as f32
+                    let (low, high) = if *self <= *other { (*self, *other) } else { (*other, *self) };
+                    let distance = high.checked_sub(low).map_or(high as f64 - low as f64, |difference| difference as f64);
+                    distance.min(f32::MAX as f64) as f32
                 }
 
                 fn add(&self, value: f32) -> Option<Self> {
-                    self.checked_add(value as $x)
+                    if value < 0.0 {
+                        self.checked_sub((-value) as $x)
+                    } else {
+                        self.checked_add(value as $x)
+                    }
                 }
 
+                fn zero() -> Option<Self> {
+                    Some(0)
+                }
+
+                //Offsets from `self` are added as integers, adding them as floats would round large values
                 fn get_values_in_between(&self, other: &Self, min_distance: f32, optimal_distance: f32) -> Vec<Self> {
                     assert!(*self <= *other);
+                    let total_distance = other.checked_sub(*self).map_or(*other as f64 - *self as f64, |difference| difference as f64);
                     let mut result: Vec<Self> = Vec::new();
                     for i in 1.. {
-                        let perfect_value: f32 = *self as f32 + optimal_distance * i as f32;
-                        let value: $x = perfect_value as $x;
-                        if value as f32 + min_distance < *other as f32 {
-                            result.push(value);
-                        } else {
+                        let offset = (optimal_distance as f64 * i as f64).trunc();
+                        if offset + min_distance as f64 >= total_distance {
                             break;
                         }
+                        match self.checked_add(offset as $x) {
+                            Some(value) => result.push(value),
+                            None => break,
+                        }
                     }
                     result
                 }
@@ -315,14 +1038,21 @@ macro_rules! float_axis_value_and_data_impl {
                 }
 
                 fn distance_to(&self, other: &Self) -> f32 {
-                    assert!(*self <= *other);
-                    (*other - *self) as f32
+                    (*other - *self).abs() as f32
                 }
 
                 fn add(&self, value: f32) -> Option<Self> {
                     Some(*self + value as $x)
                 }
 
+                fn zero() -> Option<Self> {
+                    Some(0.0)
+                }
+
+                fn is_finite(&self) -> bool {
+                    $x::is_finite(*self)
+                }
+
                 fn get_values_in_between(&self, other: &Self, min_distance: f32, optimal_distance: f32) -> Vec<Self> {
                     assert!(*self <= *other);
                     let mut result: Vec<Self> = Vec::new();
@@ -361,8 +1091,7 @@ impl AxisValue for char {
     fn distance_to(&self, other: &Self) -> f32 {
         let self_u32 = *self as u32;
         let other_u32 = *other as u32;
-        assert!(self_u32 <= other_u32);
-        (other_u32 - self_u32) as f32
+        (other_u32 as f32 - self_u32 as f32).abs()
     }
 
     fn add(&self, value: f32) -> Option<Self> {
@@ -385,7 +1114,6 @@ impl AxisValue for bool {
     }
 
     fn distance_to(&self, other: &Self) -> f32 {
-        assert!(*self <= *other);
         if *self == *other {
             0.0
         } else {
@@ -411,6 +1139,84 @@ impl AxisValue for bool {
     }
 }
 
+//Distances are in seconds
+impl AxisValue for StdDuration {
+    fn compare_value(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    fn distance_to(&self, other: &Self) -> f32 {
+        (other.as_secs_f64() - self.as_secs_f64()).abs() as f32
+    }
+
+    fn add(&self, value: f32) -> Option<Self> {
+        if value < 0.0 {
+            self.checked_sub(checked_duration(-value)?)
+        } else {
+            self.checked_add(checked_duration(value)?)
+        }
+    }
+
+    fn zero() -> Option<Self> {
+        Some(StdDuration::from_secs(0))
+    }
+
+    fn preferred_steps() -> Vec<f32> {
+        DURATION_STEPS.to_vec()
+    }
+
+    fn get_values_in_between(
+        &self,
+        other: &Self,
+        min_distance: f32,
+        optimal_distance: f32,
+    ) -> Vec<Self> {
+        assert!(*self <= *other);
+        let mut result: Vec<Self> = Vec::new();
+        let min_duration = match checked_duration(min_distance) {
+            Some(min_duration) => min_duration,
+            None => return result,
+        };
+        for i in 1.. {
+            //Values which no longer fit into a duration end the labels like the end of the range does
+            let value_opt = checked_duration(optimal_distance * i as f32).and_then(|offset| self.checked_add(offset));
+            match value_opt.and_then(|value| Some((value, value.checked_add(min_duration)?))) {
+                Some((value, end)) if end < *other => result.push(value),
+                _ => break,
+            }
+        }
+        result
+    }
+}
+
+//`StdDuration::from_secs_f32` without its panics on values which are negative, not finite or too big
+fn checked_duration(seconds: f32) -> Option<StdDuration> {
+    (seconds.is_finite() && seconds >= 0.0 && seconds < u64::MAX as f32).then(|| StdDuration::from_secs_f32(seconds))
+}
+
+//Formatted as `HH:MM:SS`, the hours don't wrap around
+impl AxisData<StdDuration> for StdDuration {
+    fn value(&self) -> &StdDuration {
+        &self
+    }
+
+    fn display_value(value: &StdDuration) -> String {
+        let seconds = value.as_secs();
+        format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+}
+
+//Seconds: a second, ten seconds, a minute, ten minutes, an hour and a day
+const DURATION_STEPS: [f32; 6] = [1.0, 10.0, 60.0, 600.0, 3_600.0, 86_400.0];
+
+//Milliseconds: subsecond decades, a second, a minute, an hour, a day, a week and a month
+#[cfg(feature = "chrono")]
+const TIME_STEPS: [f32; 9] = [1.0, 10.0, 100.0, 1_000.0, 60_000.0, 3_600_000.0, 86_400_000.0, 604_800_000.0, 2_592_000_000.0];
+
+//Days: a day, a week, a month and a year
+#[cfg(feature = "chrono")]
+const DATE_STEPS: [f32; 4] = [1.0, 7.0, 30.0, 365.0];
+
 #[cfg(feature = "chrono")]
 macro_rules! time_axis_value_impl {
     ($($x:ident),*) => {
@@ -421,12 +1227,16 @@ macro_rules! time_axis_value_impl {
                 }
             
                 fn distance_to(&self, other: &Self) -> f32 {
-                    (*other - *self).num_milliseconds() as f32
+                    (*other - *self).num_milliseconds().abs() as f32
                 }
             
                 fn add(&self, value: f32) -> Option<Self> {
                     Some(*self + chrono::Duration::milliseconds(value as i64))
                 }
+
+                fn preferred_steps() -> Vec<f32> {
+                    TIME_STEPS.to_vec()
+                }
             
                 fn get_values_in_between(
                     &self,
@@ -466,12 +1276,16 @@ macro_rules! tz_time_axis_value_impl {
                 }
             
                 fn distance_to(&self, other: &Self) -> f32 {
-                    (*other - *self).num_milliseconds() as f32
+                    (*other - *self).num_milliseconds().abs() as f32
                 }
             
                 fn add(&self, value: f32) -> Option<Self> {
                     Some(*self + chrono::Duration::milliseconds(value as i64))
                 }
+
+                fn preferred_steps() -> Vec<f32> {
+                    TIME_STEPS.to_vec()
+                }
             
                 fn get_values_in_between(
                     &self,
@@ -509,12 +1323,16 @@ macro_rules! date_axis_value_impl {
                 }
             
                 fn distance_to(&self, other: &Self) -> f32 {
-                    (*other - *self).num_days() as f32
+                    (*other - *self).num_days().abs() as f32
                 }
             
                 fn add(&self, value: f32) -> Option<Self> {
                     Some(*self + chrono::Duration::days(value as i64))
                 }
+
+                fn preferred_steps() -> Vec<f32> {
+                    DATE_STEPS.to_vec()
+                }
             
                 fn get_values_in_between(
                     &self,
@@ -554,12 +1372,16 @@ macro_rules! tz_date_axis_value_impl {
                 }
             
                 fn distance_to(&self, other: &Self) -> f32 {
-                    (*other - *self).num_days() as f32
+                    (*other - *self).num_days().abs() as f32
                 }
             
                 fn add(&self, value: f32) -> Option<Self> {
                     Some(*self + chrono::Duration::days(value as i64))
                 }
+
+                fn preferred_steps() -> Vec<f32> {
+                    DATE_STEPS.to_vec()
+                }
             
                 fn get_values_in_between(
                     &self,
@@ -607,3 +1429,118 @@ default_axis_data_impl!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize,
 
 #[cfg(feature = "chrono")]
 default_axis_data_impl!(NaiveTime, NaiveDateTime, NaiveDate);
+
+#[cfg(feature = "decimal")]
+impl AxisValue for Decimal {
+    fn compare_value(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    //Exact until the conversion of the difference, differences too big for a decimal end at `f32::MAX`
+    fn distance_to(&self, other: &Self) -> f32 {
+        other.checked_sub(*self)
+            .and_then(|difference| difference.abs().to_f32())
+            .unwrap_or(f32::MAX)
+    }
+
+    fn add(&self, value: f32) -> Option<Self> {
+        self.checked_add(Decimal::from_f32(value)?)
+    }
+
+    fn zero() -> Option<Self> {
+        Some(Decimal::ZERO)
+    }
+
+    fn get_values_in_between(&self, other: &Self, min_distance: f32, optimal_distance: f32) -> Vec<Self> {
+        assert!(*self <= *other);
+        let (min_distance, optimal_distance) = match (Decimal::from_f32(min_distance), Decimal::from_f32(optimal_distance)) {
+            (Some(min_distance), Some(optimal_distance)) => (min_distance, optimal_distance),
+            _ => return Vec::new(),
+        };
+        let mut result: Vec<Self> = Vec::new();
+        for i in 1.. {
+            //Overflowing values end the labels like the end of the range does
+            let value_opt = optimal_distance
+                .checked_mul(Decimal::from(i))
+                .and_then(|offset| self.checked_add(offset));
+            match value_opt.and_then(|value| Some((value, value.checked_add(min_distance)?))) {
+                Some((value, end)) if end < *other => result.push(value),
+                _ => break,
+            }
+        }
+        result
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl AxisData<Decimal> for Decimal {
+    fn value(&self) -> &Decimal {
+        &self
+    }
+
+    //Always two decimal places, like the floats
+    fn display_value(value: &Decimal) -> String {
+        let mut value = *value;
+        value.rescale(2);
+        format!("{}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symlog_is_linear_near_zero_and_compresses_large_values() {
+        let scale = ScaleKind::SymLog { linthresh: 1.0 };
+        assert_eq!(scale.transform(0.5), 0.5);
+        assert_eq!(scale.transform(-0.25), -0.25);
+        assert!((scale.transform(1000.0) - 4.0).abs() < 1e-5);
+        assert!((scale.transform(-1000.0) + 4.0).abs() < 1e-5);
+        assert!((scale.inverse(scale.transform(1000.0)) - 1000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn grid_color_sets_both_gridline_colors() {
+        let color = Color::from_rgb8(10, 20, 30);
+        let theme = ThemeSettingsBuilder::new().grid_color(color).grid_width(2.0).build();
+        assert_eq!(theme.x_label_line_color, color);
+        assert_eq!(theme.y_label_line_color, color);
+        assert_eq!(theme.x_label_line_width, 2.0);
+        assert_eq!(theme.y_label_line_width, 2.0);
+        //Everything else is left as it was
+        assert_eq!(theme.x_label_text_color, ThemeSettings::default().x_label_text_color);
+    }
+
+    #[test]
+    fn durations_out_of_range_are_rejected_instead_of_panicking() {
+        let second = StdDuration::from_secs(1);
+        assert_eq!(second.add(f32::NAN), None);
+        assert_eq!(second.add(f32::INFINITY), None);
+        assert_eq!(second.add(1e20), None);
+        assert_eq!(second.add(-2.0), None);
+        assert_eq!(second.add(-0.5), Some(StdDuration::from_millis(500)));
+        //Labels stop where the durations stop fitting
+        let max = StdDuration::new(u64::MAX, 0);
+        assert!(StdDuration::from_secs(u64::MAX - 100).get_values_in_between(&max, 1e18, 1e18).is_empty());
+        assert_eq!(
+            StdDuration::from_secs(0).get_values_in_between(&StdDuration::from_secs(30), 5.0, 10.0),
+            vec![StdDuration::from_secs(10), StdDuration::from_secs(20)],
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn duration_values_in_between_need_ordered_ends() {
+        StdDuration::from_secs(30).get_values_in_between(&StdDuration::from_secs(0), 5.0, 10.0);
+    }
+
+    #[test]
+    fn wide_integer_distances_stay_finite() {
+        assert_eq!((-5i128).distance_to(&5), 10.0);
+        assert_eq!(5u128.distance_to(&2), 3.0);
+        assert_eq!(i128::MIN.distance_to(&i128::MAX), f32::MAX);
+        assert_eq!(0u128.distance_to(&u128::MAX), f32::MAX);
+        assert_eq!(u128::MAX.distance_to(&0), f32::MAX);
+    }
+}
@@ -0,0 +1,126 @@
+use iced::{Column, Element, Length, Row};
+use iced::canvas::Canvas;
+
+use super::data::{AxisData, AxisValue, Message, PlotSettings, Settings};
+use super::{Chart, ChartBuilder};
+
+//Builds small multiples: one chart per category, laid out in a grid, all of them with the same bounds
+pub struct FacetChartBuilder<XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> {
+    settings: Settings, //Shared by every chart, the title is replaced by the category
+    plot_settings: PlotSettings,
+    columns: usize,
+    facets: Vec<(String, Vec<(XD, YD)>)>,
+    _values: std::marker::PhantomData<(XV, YV)>,
+}
+
+impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> FacetChartBuilder<XV, YV, XD, YD> {
+    pub fn new(settings: Settings, plot_settings: PlotSettings) -> Self {
+        Self {
+            settings,
+            plot_settings,
+            columns: 2,
+            facets: Vec::new(),
+            _values: std::marker::PhantomData,
+        }
+    }
+
+    //Number of charts in a row of the grid, at least one
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = columns.max(1);
+        self
+    }
+
+    pub fn add_facet(mut self, category: String, edges: Vec<(XD, YD)>) -> Self {
+        self.facets.push((category, edges));
+        self
+    }
+
+    //Panics if there is no data at all, like `ChartBuilder::calculate_min_max_values`
+    pub fn build(self) -> FacetChart<XV, YV, XD, YD> {
+        //The bounds are calculated over all of the facets at once
+        let shared = ChartBuilder::new(self.settings.clone())
+            .data(
+                self.facets
+                    .iter()
+                    .map(|(_category, edges)| (self.plot_settings.clone(), edges.clone()))
+                    .collect(),
+            )
+            .calculate_min_max_values();
+        let min_x_value = shared.min_x_value_opt.unwrap();
+        let max_x_value = shared.max_x_value_opt.unwrap();
+        let min_y_value = shared.min_y_value_opt.unwrap();
+        let max_y_value = shared.max_y_value_opt.unwrap();
+        let settings = &self.settings;
+        let plot_settings = &self.plot_settings;
+        let charts = self.facets
+            .into_iter()
+            .map(|(category, edges)| {
+                let settings = Settings {
+                    title: Some(category),
+                    ..settings.clone()
+                };
+                ChartBuilder::new(settings)
+                    .add_data(plot_settings.clone(), edges)
+                    .min_x_value(min_x_value.clone())
+                    .max_x_value(max_x_value.clone())
+                    .min_y_value(min_y_value.clone())
+                    .max_y_value(max_y_value.clone())
+                    .build()
+            })
+            .collect();
+        FacetChart {
+            columns: self.columns,
+            charts,
+        }
+    }
+}
+
+pub struct FacetChart<XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> {
+    pub columns: usize,
+    pub charts: Vec<Chart<XV, YV, XD, YD>>, //In the order the facets were added
+}
+
+impl <XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> FacetChart<XV, YV, XD, YD> {
+    //A grid of canvases filling the available space, row by row.
+    //The canvases borrow the charts, so they keep their state between frames.
+    pub fn view(&mut self) -> Element<'_, Message> {
+        let columns = self.columns;
+        let mut grid = Column::new().width(Length::Fill).height(Length::Fill);
+        let mut row = Row::new().width(Length::Fill).height(Length::Fill);
+        let mut in_row = 0;
+        for chart in self.charts.iter_mut() {
+            row = row.push(Canvas::new(chart).width(Length::Fill).height(Length::Fill));
+            in_row += 1;
+            if in_row == columns {
+                grid = grid.push(row);
+                row = Row::new().width(Length::Fill).height(Length::Fill);
+                in_row = 0;
+            }
+        }
+        if in_row > 0 {
+            grid = grid.push(row);
+        }
+        grid.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_category_gets_a_chart_with_the_shared_y_range() {
+        let mut facet_chart = FacetChartBuilder::new(Settings::default(), PlotSettings::default())
+            .add_facet("a".to_owned(), vec![(0.0f32, 1.0f32), (1.0, 2.0)])
+            .add_facet("b".to_owned(), vec![(0.0, -3.0), (1.0, 0.0)])
+            .add_facet("c".to_owned(), vec![(0.0, 5.0), (2.0, 8.0)])
+            .build();
+        assert_eq!(facet_chart.charts.len(), 3);
+        let y_bounds = facet_chart.charts[0].y_bounds();
+        assert!(*y_bounds.0 <= -3.0 && *y_bounds.1 >= 8.0);
+        assert!(facet_chart.charts.iter().all(|chart| chart.y_bounds() == y_bounds));
+        //The view only borrows the charts
+        let _element = facet_chart.view();
+        assert_eq!(facet_chart.charts.len(), 3);
+    }
+}
@@ -0,0 +1,65 @@
+//Inserts `separator` between the groups of three digits of the integer part of a number ("1234567" -> "1,234,567").
+//Texts which aren't plain numbers (dates, "inf", ...) are returned unchanged.
+pub fn group_thousands(text: &str, separator: char) -> String {
+    if text.parse::<f64>().is_err() {
+        return text.to_string();
+    }
+    let (sign, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text),
+    };
+    let (integer, rest) = match unsigned.find(|c: char| c == '.' || c == 'e' || c == 'E') {
+        Some(index) => unsigned.split_at(index),
+        None => (unsigned, ""),
+    };
+    if !integer.chars().all(|c| c.is_ascii_digit()) {
+        return text.to_string();
+    }
+    let mut grouped = String::new();
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    format!("{}{}{}", sign, grouped, rest)
+}
+
+//Rewrites a plain number as `1.20e9` if its magnitude is at least `threshold` or (except zero) below `1 / threshold`.
+//Returns `None` for other texts and for numbers which are fine as they are.
+pub fn scientific(text: &str, threshold: f64, precision: usize) -> Option<String> {
+    let value: f64 = text.parse().ok()?;
+    let magnitude = value.abs();
+    let too_big = magnitude >= threshold;
+    let too_small = magnitude != 0.0 && magnitude < 1.0 / threshold;
+    (value.is_finite() && (too_big || too_small)).then(|| format!("{:.*e}", precision, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thousands_are_grouped() {
+        assert_eq!(group_thousands("1234567", ','), "1,234,567");
+        assert_eq!(group_thousands("-1234567.25", ' '), "-1 234 567.25");
+        assert_eq!(group_thousands("123", ','), "123");
+        assert_eq!(group_thousands("12:30", ','), "12:30");
+    }
+
+    #[test]
+    fn scientific_notation_starts_at_the_threshold() {
+        assert_eq!(scientific("1000000", 1e6, 2), Some("1.00e6".to_owned()));
+        assert_eq!(scientific("999999", 1e6, 2), None);
+        assert_eq!(scientific("-2500000", 1e6, 1), Some("-2.5e6".to_owned()));
+    }
+
+    #[test]
+    fn scientific_notation_for_small_values_has_negative_exponents() {
+        assert_eq!(scientific("0.0000003", 1e6, 2), Some("3.00e-7".to_owned()));
+        assert_eq!(scientific("-0.0000003", 1e6, 2), Some("-3.00e-7".to_owned()));
+        assert_eq!(scientific("0.000001", 1e6, 2), None);
+        assert_eq!(scientific("0", 1e6, 2), None);
+        assert_eq!(scientific("12:30", 1e6, 2), None);
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt::Write;
+
+use iced::{Color, Point, Rectangle, Size};
+use iced::{HorizontalAlignment, VerticalAlignment};
+
+use super::data::{AxisData, AxisValue, LineStyle, YAxis};
+use super::{extreme_points, marker_vertices, rounded_corner_radius, Chart, LineLayer};
+
+impl<XV: AxisValue, YV: AxisValue, XD: AxisData<XV>, YD: AxisData<YV>> Chart<XV, YV, XD, YD> {
+    //A standalone SVG document with the backgrounds, the title, the labels of both y axes with their gridlines,
+    //the normal band and the plots: area fills, dashed or dotted lines, markers, crossings and extremes.
+    //Left out are the interactive parts (tooltips, selection and its emphasis, the crosshair, highlighted gridlines),
+    //the minor gridlines, the axis titles, the x axis at zero, the reference and identity lines, the confidence ellipses,
+    //point labels, the stats, the legend and the watermark.
+    //Lines are straight and in their line color (no smoothing or gradients), points aren't decimated,
+    //and fills get the color of their blend mode but are composed normally.
+    pub fn render_to_svg(&self, size: Size) -> String {
+        let theme = &self.settings.theme;
+        let full_area = Rectangle::new(Point::ORIGIN, size);
+        let padded_area = self.settings.padding.transform(full_area);
+        let margined_area = self.settings.margin.transform(padded_area);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = size.width,
+            h = size.height,
+        );
+        rect(&mut svg, full_area, theme.background_color, None);
+        rect(&mut svg, padded_area, theme.padded_background_color, rounded_corner_radius(padded_area, theme.background_corner_radius));
+        if let Some(margined_background_color) = theme.margined_background_color {
+            rect(&mut svg, margined_area, margined_background_color, rounded_corner_radius(margined_area, theme.background_corner_radius));
+        }
+
+        //Title
+        if let Some(title) = &self.settings.title {
+            let (position, _subtitle_position, horizontal_alignment) = self.title_positions(size);
+            text(&mut svg, title, position, theme.title_color, theme.title_size, horizontal_alignment, VerticalAlignment::Center);
+        }
+        if let Some(subtitle) = &self.settings.subtitle {
+            let (_title_position, position, horizontal_alignment) = self.title_positions(size);
+            text(&mut svg, subtitle, position, theme.title_color, theme.subtitle_size, horizontal_alignment, VerticalAlignment::Center);
+        }
+
+        //Y gridlines and labels
+        let mirror = |x: f32| if self.settings.rtl { size.width - x } else { x };
+        for (y, label) in self.y_labels(margined_area) {
+            //The tick mark and the gridline as one line
+            let x1 = if theme.y_ticks { padded_area.x } else { margined_area.x };
+            let x2 = if theme.y_gridlines { margined_area.x + margined_area.width } else { margined_area.x };
+            if x1 < x2 {
+                line(&mut svg, Point::new(mirror(x1), y), Point::new(mirror(x2), y), theme.y_label_line_color, theme.y_label_line_width);
+            }
+            let alignment = theme.y_label_alignment;
+            let x = match alignment.horizontal {
+                HorizontalAlignment::Left => 5.0,
+                HorizontalAlignment::Center => padded_area.x / 2.0,
+                HorizontalAlignment::Right => padded_area.x - 5.0,
+            };
+            let horizontal_alignment = if self.settings.rtl {
+                super::mirror_alignment(alignment.horizontal)
+            } else {
+                alignment.horizontal
+            };
+            let position = Point::new(mirror(x), y);
+            text(&mut svg, &label, position, theme.y_label_text_color, theme.y_label_text_size, horizontal_alignment, alignment.vertical);
+        }
+
+        //X gridlines and labels
+        for (x, label) in self.x_labels(margined_area) {
+            let margined_bottom = margined_area.y + margined_area.height;
+            let y1 = if theme.x_ticks { size.height - padded_area.y } else { margined_bottom };
+            let y2 = if theme.x_gridlines { margined_area.y } else { margined_bottom };
+            if y1 > y2 {
+                line(&mut svg, Point::new(x, y1), Point::new(x, y2), theme.x_label_line_color, theme.x_label_line_width);
+            }
+            let alignment = theme.x_label_alignment;
+            let y = match alignment.vertical {
+                VerticalAlignment::Top => size.height - padded_area.y + 5.0,
+                VerticalAlignment::Center => size.height - padded_area.y / 2.0,
+                VerticalAlignment::Bottom => size.height - 5.0,
+            };
+            let position = Point::new(x, y);
+            text(&mut svg, &label, position, theme.x_label_text_color, theme.x_label_text_size, alignment.horizontal, alignment.vertical);
+        }
+
+        //Secondary y labels, on the other side with only a tick mark
+        if self.secondary_y_range_opt.is_some() {
+            for (y, label) in self.axis_y_labels(YAxis::Secondary, margined_area) {
+                let geometry = self.y_label_geometry(size, padded_area, margined_area, YAxis::Secondary, y, &label);
+                for (from, to) in geometry.lines {
+                    line(&mut svg, from, to, theme.y_label_line_color, theme.y_label_line_width);
+                }
+                let label_text = geometry.text;
+                text(
+                    &mut svg,
+                    &label_text.content,
+                    label_text.position,
+                    label_text.color,
+                    label_text.size,
+                    label_text.horizontal_alignment,
+                    label_text.vertical_alignment,
+                );
+            }
+        }
+
+        //Normal band
+        if let Some((band_area, color)) = self.normal_band_area(margined_area) {
+            rect(&mut svg, band_area, color, None);
+        }
+
+        //Plots
+        let _ = writeln!(svg, r#"<g transform="translate({} {})">"#, margined_area.x, margined_area.y);
+        //Clipped to the margined area like on the canvas
+        let plot_area = Rectangle::new(Point::ORIGIN, margined_area.size());
+        for ((plot_settings, edges), vec) in self.data.iter().zip(self.points(margined_area.size()).iter()) {
+            let line_color = plot_settings.theme.line_color;
+            //Every point is drawn, so there is no ghost
+            let runs = self
+                .line_layers(vec, vec, plot_area)
+                .into_iter()
+                .find_map(|line_layer| match line_layer {
+                    LineLayer::Line(runs) => Some(runs),
+                    LineLayer::Ghost(_segments) => None,
+                })
+                .unwrap_or_default();
+            if let Some(fill_color) = plot_settings.theme.fill_color {
+                let baseline = self.baseline_y(plot_settings.axis, plot_area.height);
+                for (polygon, color) in self.fill_geometry(&runs, baseline, fill_color) {
+                    let (color, opacity) = svg_color(color);
+                    let _ = writeln!(
+                        svg,
+                        r#"<polygon points="{}" fill="{}" fill-opacity="{}"/>"#,
+                        coords(&polygon),
+                        color,
+                        opacity,
+                    );
+                }
+            }
+            let pattern = match plot_settings.theme.line_style {
+                LineStyle::Solid => String::new(),
+                LineStyle::Dashed { dash, gap } => format!(r#" stroke-dasharray="{} {}""#, dash, gap),
+                LineStyle::Dotted { spacing } => format!(r#" stroke-dasharray="0 {}" stroke-linecap="round""#, spacing),
+            };
+            for (_offset, run) in runs.iter().filter(|(_offset, run)| run.len() >= 2) {
+                let (color, opacity) = svg_color(line_color);
+                let _ = writeln!(
+                    svg,
+                    r#"<polyline points="{}" fill="none" stroke="{}" stroke-opacity="{}" stroke-width="{}"{}/>"#,
+                    coords(run),
+                    color,
+                    opacity,
+                    plot_settings.line_size1,
+                    pattern,
+                );
+            }
+            for crossing in self.crossing_markers(plot_settings.axis, vec, plot_area) {
+                ring(&mut svg, crossing, plot_settings.point_size2, line_color);
+            }
+            if self.marks_extremes() {
+                if let Some(((highest_index, highest), (lowest_index, lowest))) = extreme_points(vec, plot_area) {
+                    let tags = [(highest_index, highest, -1.0, VerticalAlignment::Bottom), (lowest_index, lowest, 1.0, VerticalAlignment::Top)];
+                    for (point_index, point, direction, vertical_alignment) in tags.iter() {
+                        ring(&mut svg, *point, plot_settings.point_size3, line_color);
+                        let (_xd, yd) = &edges[*point_index];
+                        let position = Point::new(point.x, point.y + direction * (plot_settings.point_size3 + 2.0));
+                        text(&mut svg, &self.describe_y(yd), position, theme.data_description_color, theme.stats_size, HorizontalAlignment::Center, *vertical_alignment);
+                    }
+                }
+            }
+            for (point_index, point) in vec.iter().enumerate().filter(|(_point_index, point)| plot_area.contains(**point)) {
+                let point_color = plot_settings.point_colors
+                    .as_ref()
+                    .and_then(|point_colors| point_colors.get(point_index))
+                    .copied()
+                    .unwrap_or(plot_settings.theme.point_color);
+                let (color, opacity) = svg_color(point_color);
+                match marker_vertices(plot_settings.theme.marker, plot_settings.point_size1) {
+                    Some(vertices) => {
+                        let corners: Vec<Point> = vertices.iter().map(|(dx, dy)| Point::new(point.x + dx, point.y + dy)).collect();
+                        let _ = writeln!(
+                            svg,
+                            r#"<polygon points="{}" fill="{}" fill-opacity="{}"/>"#,
+                            coords(&corners),
+                            color,
+                            opacity,
+                        );
+                    }
+                    None => {
+                        let _ = writeln!(
+                            svg,
+                            r#"<circle cx="{}" cy="{}" r="{}" fill="{}" fill-opacity="{}"/>"#,
+                            point.x,
+                            point.y,
+                            plot_settings.point_size1,
+                            color,
+                            opacity,
+                        );
+                    }
+                }
+            }
+        }
+        let _ = writeln!(svg, "</g>");
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+//Color as "rgb(r,g,b)" and its opacity separately, which every SVG viewer understands
+fn svg_color(color: Color) -> (String, f32) {
+    let [r, g, b, a] = color.into_rgba8();
+    (format!("rgb({},{},{})", r, g, b), a as f32 / 255.0)
+}
+
+fn rect(svg: &mut String, area: Rectangle, color: Color, corner_radius_opt: Option<f32>) {
+    let (color, opacity) = svg_color(color);
+    let corners = corner_radius_opt
+        .map(|radius| format!(r#" rx="{r}" ry="{r}""#, r = radius))
+        .unwrap_or_default();
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}"{} fill="{}" fill-opacity="{}"/>"#,
+        area.x, area.y, area.width, area.height, corners, color, opacity,
+    );
+}
+
+//Points as the value of a `points` attribute
+fn coords(points: &[Point]) -> String {
+    let coords: Vec<String> = points.iter().map(|point| format!("{},{}", point.x, point.y)).collect();
+    coords.join(" ")
+}
+
+//The outline of a circle, like the crossing and extreme markers
+fn ring(svg: &mut String, center: Point, radius: f32, color: Color) {
+    let (color, opacity) = svg_color(color);
+    let _ = writeln!(
+        svg,
+        r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-opacity="{}" stroke-width="1"/>"#,
+        center.x, center.y, radius, color, opacity,
+    );
+}
+
+fn line(svg: &mut String, from: Point, to: Point, color: Color, width: f32) {
+    let (color, opacity) = svg_color(color);
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-opacity="{}" stroke-width="{}"/>"#,
+        from.x, from.y, to.x, to.y, color, opacity, width,
+    );
+}
+
+fn text(
+    svg: &mut String,
+    content: &str,
+    position: Point,
+    color: Color,
+    size: f32,
+    horizontal_alignment: HorizontalAlignment,
+    vertical_alignment: VerticalAlignment,
+) {
+    let anchor = match horizontal_alignment {
+        HorizontalAlignment::Left => "start",
+        HorizontalAlignment::Center => "middle",
+        HorizontalAlignment::Right => "end",
+    };
+    let baseline = match vertical_alignment {
+        VerticalAlignment::Top => "hanging",
+        VerticalAlignment::Center => "middle",
+        VerticalAlignment::Bottom => "text-after-edge",
+    };
+    let (color, opacity) = svg_color(color);
+    //Every line of the text becomes a line of its own
+    for (i, content_line) in content.lines().enumerate() {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="{}" fill="{}" fill-opacity="{}" text-anchor="{}" dominant-baseline="{}">{}</text>"#,
+            position.x,
+            position.y + i as f32 * size * 1.2,
+            size,
+            color,
+            opacity,
+            anchor,
+            baseline,
+            escape(content_line),
+        );
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,1123 @@
+use iced::{Point, Rectangle, Size};
+
+use super::*;
+
+//A single plot from (0, 0) to (10, 10), with the bounds set to its values
+fn chart_0_10(settings: Settings) -> Chart<f32, f32, f32, f32> {
+    ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .calculate_min_max_values()
+        .build()
+}
+
+#[test]
+fn rounded_background_when_radius_is_nonzero() {
+    let area = Rectangle::new(Point::new(10.0, 10.0), Size::new(200.0, 100.0));
+    assert_eq!(rounded_corner_radius(area, 8.0), Some(8.0));
+    assert_eq!(rounded_corner_radius(area, 0.0), None);
+    //Clamped to half of the shorter side
+    assert_eq!(rounded_corner_radius(area, 80.0), Some(50.0));
+}
+
+#[cfg(feature = "export")]
+#[test]
+fn svg_backgrounds_have_rounded_corners() {
+    let mut settings = Settings::default();
+    settings.theme.background_corner_radius = 6.0;
+    let chart = chart_0_10(settings);
+    let svg = chart.render_to_svg(Size::new(400.0, 300.0));
+    assert!(svg.contains(r#"rx="6" ry="6""#));
+}
+
+#[test]
+fn label_alignment_is_applied_to_the_text() {
+    let mut settings = Settings::default();
+    settings.theme.y_label_alignment = data::LabelAlignment {
+        horizontal: HorizontalAlignment::Left,
+        vertical: VerticalAlignment::Top,
+    };
+    settings.theme.x_label_alignment = data::LabelAlignment {
+        horizontal: HorizontalAlignment::Right,
+        vertical: VerticalAlignment::Bottom,
+    };
+    let chart = chart_0_10(settings);
+    let size = Size::new(400.0, 300.0);
+    let padded_area = chart.settings.padding.transform(Rectangle::new(Point::ORIGIN, size));
+    let margined_area = chart.settings.margin.transform(padded_area);
+
+    let y_label = chart.y_label_geometry(size, padded_area, margined_area, YAxis::Primary, 150.0, "5");
+    assert_eq!(y_label.text.horizontal_alignment, HorizontalAlignment::Left);
+    assert_eq!(y_label.text.vertical_alignment, VerticalAlignment::Top);
+    assert_eq!(y_label.text.position, Point::new(5.0, 150.0));
+
+    let x_label = chart.x_label_geometry(size, padded_area, margined_area, 200.0, "5");
+    assert_eq!(x_label.text.horizontal_alignment, HorizontalAlignment::Right);
+    assert_eq!(x_label.text.vertical_alignment, VerticalAlignment::Bottom);
+    assert_eq!(x_label.text.position, Point::new(200.0, 295.0));
+}
+
+#[test]
+fn stats_text_has_the_count_and_extremes() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 3.0f32), (1.0, -1.0), (2.0, 8.0)])
+        .calculate_min_max_values()
+        .build();
+    assert_eq!(
+        chart.stats_text(),
+        Some("n: 3  min: -1.00  max: 8.00  mean: 3.33".to_owned()),
+    );
+}
+
+#[test]
+fn nearest_readout_between_points() {
+    let settings = Settings {
+        readout_mode: ReadoutMode::Nearest,
+        ..Default::default()
+    };
+    let chart = chart_0_10(settings);
+    assert_eq!(chart.readout(&4.0), vec![Some(0.0)]);
+    assert_eq!(chart.crosshair_y_text(&4.0), "0.00");
+}
+
+#[test]
+fn interpolated_readout_between_points() {
+    let settings = Settings {
+        readout_mode: ReadoutMode::Interpolate,
+        ..Default::default()
+    };
+    let chart = chart_0_10(settings);
+    assert_eq!(chart.readout(&4.0), vec![Some(4.0)]);
+    assert_eq!(chart.crosshair_y_text(&4.0), "4.00");
+}
+
+#[test]
+fn no_readout_between_points() {
+    let settings = Settings {
+        readout_mode: ReadoutMode::None,
+        ..Default::default()
+    };
+    let chart = chart_0_10(settings);
+    assert_eq!(chart.readout(&4.0), vec![None]);
+    assert_eq!(chart.crosshair_y_text(&4.0), "no data");
+}
+
+#[test]
+fn fill_blend_is_applied_to_the_fill_geometry() {
+    let settings = Settings {
+        fill_blend: data::BlendMode::Additive,
+        ..Default::default()
+    };
+    let chart = chart_0_10(settings);
+    let runs = vec![(0, vec![Point::new(0.0, 50.0), Point::new(100.0, 20.0)])];
+    let fill_color = Color::from_rgba(0.2, 0.4, 0.6, 0.8);
+    let geometry = chart.fill_geometry(&runs, 100.0, fill_color);
+    assert_eq!(geometry.len(), 1);
+    let (polygon, color) = &geometry[0];
+    assert_eq!(
+        polygon,
+        &vec![Point::new(0.0, 100.0), Point::new(0.0, 50.0), Point::new(100.0, 20.0), Point::new(100.0, 100.0)],
+    );
+    assert_eq!(*color, data::BlendMode::Additive.apply(fill_color));
+    assert_eq!(color.a, 0.4);
+}
+
+#[test]
+fn labeled_ticks_are_drawn_with_their_text() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .calculate_min_max_values()
+        .add_x_labeled_tick(5.0, "release".to_owned())
+        .add_x_labeled_tick(20.0, "outside".to_owned())
+        .build();
+    let margined_area = chart.margined_area(Size::new(400.0, 300.0));
+    let x_labels = chart.x_labels(margined_area);
+    assert!(x_labels.contains(&(200.0, "release".to_owned())));
+    assert!(x_labels.iter().all(|(_x, text)| text != "outside"));
+}
+
+#[test]
+fn progressive_detail_refines_on_tick() {
+    let settings = Settings {
+        progressive_detail: Some(data::ProgressiveDetail {
+            initial_points: 100,
+            budget: std::time::Duration::from_secs(10),
+        }),
+        ..Default::default()
+    };
+    let edges: Vec<(f32, f32)> = (0..10_000).map(|i| (i as f32, (i as f32 / 100.0).sin())).collect();
+    let mut chart = ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), edges)
+        .calculate_min_max_values()
+        .build();
+    let bounds = Rectangle::new(Point::ORIGIN, Size::new(400.0, 300.0));
+
+    chart.draw(bounds, Cursor::Unavailable);
+    assert_eq!(chart.detail_level.get(), 1);
+    assert!(chart.is_refining());
+    chart.tick();
+    chart.draw(bounds, Cursor::Unavailable);
+    assert_eq!(chart.detail_level.get(), 2);
+
+    //Changing the view starts over
+    chart.set_view_state(chart.view_state());
+    assert_eq!(chart.detail_level.get(), 0);
+}
+
+#[test]
+fn nearest_point_in_plot_ignores_the_other_plots() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (5.0, 5.5), (10.0, 0.0)])
+        .add_data(PlotSettings::default(), vec![(0.0f32, 10.0f32), (5.0, 5.0), (10.0, 10.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let size = Size::new(400.0, 300.0);
+    //Exactly at the point of the second plot, 7 pixels below the one of the first plot
+    let cursor_position = Point::new(200.0, 150.0);
+    assert_eq!(chart.nearest_point_in_plot(0, cursor_position, size), Some((1, 5.0, 5.5)));
+    assert_eq!(chart.nearest_point_in_plot(1, cursor_position, size), Some((1, 5.0, 5.0)));
+    assert_eq!(chart.nearest_point_in_plot(2, cursor_position, size), None);
+}
+
+#[test]
+fn gradient_series_colors_are_evenly_interpolated() {
+    let builder: ChartBuilder<f32, f32, f32, f32> = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0, 0.0)])
+        .add_data(PlotSettings::default(), vec![(0.0, 1.0)])
+        .add_data(PlotSettings::default(), vec![(0.0, 2.0)])
+        .gradient_series_colors(Color::BLACK, Color::WHITE);
+    let colors: Vec<Color> = builder.data.iter().map(|(plot_settings, _edges)| plot_settings.theme.line_color).collect();
+    assert_eq!(colors, vec![Color::BLACK, Color::from_rgb(0.5, 0.5, 0.5), Color::WHITE]);
+    assert!(builder.data.iter().all(|(plot_settings, _edges)| plot_settings.theme.point_color == plot_settings.theme.line_color));
+}
+
+#[test]
+fn mirrored_tick_marks_on_the_right_and_top() {
+    let mut settings = Settings::default();
+    settings.theme.mirror_ticks = true;
+    let chart = chart_0_10(settings);
+    let size = Size::new(400.0, 300.0);
+    let padded_area = chart.settings.padding.transform(Rectangle::new(Point::ORIGIN, size));
+    let margined_area = chart.settings.margin.transform(padded_area);
+
+    let y_label = chart.y_label_geometry(size, padded_area, margined_area, YAxis::Primary, 150.0, "5");
+    assert!(y_label.lines.contains(&(Point::new(60.0, 150.0), Point::new(80.0, 150.0))));
+    assert!(y_label.lines.contains(&(Point::new(320.0, 150.0), Point::new(340.0, 150.0))));
+
+    let x_label = chart.x_label_geometry(size, padded_area, margined_area, 200.0, "5");
+    assert!(x_label.lines.contains(&(Point::new(200.0, 240.0), Point::new(200.0, 220.0))));
+    assert!(x_label.lines.contains(&(Point::new(200.0, 80.0), Point::new(200.0, 60.0))));
+
+    //Only the regular tick mark and the gridline without mirroring
+    let mut settings = Settings::default();
+    settings.theme.mirror_ticks = false;
+    let chart = chart_0_10(settings);
+    let y_label = chart.y_label_geometry(size, padded_area, margined_area, YAxis::Primary, 150.0, "5");
+    assert_eq!(y_label.lines.len(), 2);
+}
+
+#[test]
+fn validate_reports_nan_and_unsorted_x() {
+    let builder: ChartBuilder<f32, f32, f32, f32> = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0, 0.0), (1.0, f32::NAN), (2.0, 2.0)])
+        .add_data(PlotSettings::default(), vec![(0.0, 0.0), (2.0, 1.0), (1.0, 2.0)]);
+    assert_eq!(
+        builder.validate(),
+        vec![
+            ValidationWarning::NonFiniteValue { plot_index: 0, point_index: 1 },
+            ValidationWarning::UnsortedX { plot_index: 1, point_index: 2 },
+        ],
+    );
+}
+
+#[test]
+fn dim_others_lowers_the_alpha_of_unselected_plots() {
+    let settings = Settings {
+        selection_emphasis: SelectionEmphasis::DimOthers,
+        ..Default::default()
+    };
+    let chart = ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .add_data(PlotSettings::default(), vec![(0.0f32, 10.0f32), (10.0, 0.0)])
+        .calculate_min_max_values()
+        .build();
+    assert_eq!(chart.emphasis_alpha(0, Some(0)), 1.0);
+    assert!(chart.emphasis_alpha(1, Some(0)) < 1.0);
+    //Nothing is dimmed while nothing is hovered
+    assert_eq!(chart.emphasis_alpha(1, None), 1.0);
+
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .add_data(PlotSettings::default(), vec![(0.0f32, 10.0f32), (10.0, 0.0)])
+        .calculate_min_max_values()
+        .build();
+    assert_eq!(chart.emphasis_alpha(1, Some(0)), 1.0);
+}
+
+#[test]
+fn rtl_puts_the_y_labels_on_the_right() {
+    let settings = Settings {
+        rtl: true,
+        ..Default::default()
+    };
+    let chart = chart_0_10(settings);
+    let size = Size::new(400.0, 300.0);
+    let padded_area = chart.settings.padding.transform(Rectangle::new(Point::ORIGIN, size));
+    let margined_area = chart.settings.margin.transform(padded_area);
+    let y_label = chart.y_label_geometry(size, padded_area, margined_area, YAxis::Primary, 150.0, "5");
+    assert_eq!(y_label.text.position, Point::new(345.0, 150.0));
+    assert_eq!(y_label.text.horizontal_alignment, HorizontalAlignment::Left);
+    assert!(y_label.lines.contains(&(Point::new(340.0, 150.0), Point::new(320.0, 150.0))));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn log_time_ticks_are_decades_of_milliseconds() {
+    use chrono::{Duration, NaiveTime};
+
+    let midnight = NaiveTime::from_hms(0, 0, 0);
+    //1ms..1s on 1000 pixels, the decades at the ends are too close to them to be labeled
+    let ticks = scale_tick_values(ScaleKind::Log { floor: 1.0 }, &midnight, 1000.0, 1000.0, 50.0);
+    assert_eq!(ticks, vec![midnight + Duration::milliseconds(10), midnight + Duration::milliseconds(100)]);
+}
+
+#[test]
+fn log_duration_ticks_are_decades() {
+    use std::time::Duration;
+
+    let min = Duration::from_millis(1);
+    let total_distance = min.distance_to(&Duration::from_secs(1));
+    let ticks = scale_tick_values(ScaleKind::Log { floor: 0.001 }, &min, total_distance, 1000.0, 50.0);
+    let ticks_ms: Vec<u128> = ticks.iter().map(|tick| (tick.as_secs_f64() * 1000.0).round() as u128).collect();
+    assert_eq!(ticks_ms, vec![10, 100]);
+}
+
+#[test]
+fn view_state_restores_the_zoom_after_reset() {
+    let mut chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (5.0, 2.0), (10.0, 10.0)])
+        .calculate_min_max_values()
+        .build();
+    chart.set_view_state(ViewState {
+        min_x_value: 2.0,
+        max_x_value: 4.0,
+        min_y_value: 1.0,
+        max_y_value: 3.0,
+    });
+    let zoomed = chart.view_state();
+
+    chart.reset_view();
+    assert_eq!(chart.x_bounds(), (&0.0, &10.0));
+    assert_eq!(chart.y_bounds(), (&0.0, &10.0));
+
+    chart.set_view_state(zoomed.clone());
+    assert_eq!(chart.x_bounds(), (&2.0, &4.0));
+    assert_eq!(chart.y_bounds(), (&1.0, &3.0));
+    assert_eq!(chart.view_state(), zoomed);
+}
+
+#[test]
+fn ticks_snap_to_preferred_steps() {
+    //Byte counts, ticked at whole kibibytes
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Bytes(u64);
+
+    impl AxisValue for Bytes {
+        fn compare_value(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+
+        fn distance_to(&self, other: &Self) -> f32 {
+            (self.0 as f32 - other.0 as f32).abs()
+        }
+
+        fn add(&self, value: f32) -> Option<Self> {
+            Some(Bytes((self.0 as f32 + value) as u64))
+        }
+
+        fn preferred_steps() -> Vec<f32> {
+            vec![1024.0, 65536.0]
+        }
+
+        fn get_values_in_between(&self, other: &Self, min_distance: f32, optimal_distance: f32) -> Vec<Self> {
+            (1..)
+                .map(|i| Bytes(self.0 + (optimal_distance * i as f32) as u64))
+                .take_while(|value| value.0 as f32 + min_distance < other.0 as f32)
+                .collect()
+        }
+    }
+
+    impl AxisData<Bytes> for Bytes {
+        fn value(&self) -> &Bytes {
+            self
+        }
+
+        fn display_value(value: &Bytes) -> String {
+            format!("{}", value.0)
+        }
+    }
+
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(Bytes(0), 0.0f32), (Bytes(10_000), 1.0)])
+        .min_x_value(Bytes(0))
+        .max_x_value(Bytes(10_000))
+        .calculate_min_max_y_values()
+        .build();
+    //Wide enough for labels closer than the biggest step
+    let x_labels = chart.x_labels(chart.margined_area(Size::new(2000.0, 300.0)));
+    let ticks: Vec<u64> = x_labels.iter().map(|(_x, text)| text.parse().unwrap()).collect();
+    //The ends are labeled too
+    assert_eq!(ticks.first(), Some(&0));
+    assert_eq!(ticks.last(), Some(&10_000));
+    let in_between = &ticks[1..ticks.len() - 1];
+    assert!(!in_between.is_empty());
+    assert!(in_between.iter().all(|tick| tick % 1024 == 0));
+}
+
+#[test]
+fn legend_entries_show_the_point_counts() {
+    let settings = Settings {
+        legend_show_counts: true,
+        ..Default::default()
+    };
+    let plot_settings = PlotSettings {
+        name: Some("sensor".to_owned()),
+        ..Default::default()
+    };
+    let chart = ChartBuilder::new(settings)
+        .add_data(plot_settings, vec![(0.0f32, 0.0f32), (1.0, 1.0), (2.0, 0.0)])
+        .calculate_min_max_values()
+        .build();
+    assert_eq!(chart.legend_entries(), vec![(PlotThemeSettings::default().line_color, "sensor (3)".to_owned())]);
+}
+
+#[test]
+fn residuals_are_data_minus_fit() {
+    let builder = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 1.0f32), (1.0, 0.5), (2.0, 5.0), (3.0, 2.0)])
+        //The fit y = x
+        .add_data(PlotSettings::default(), vec![(0.0, 0.0), (2.0, 2.0)])
+        .add_residuals(0, 1, PlotSettings::default());
+    //The point at x = 3 is outside of the fit
+    assert_eq!(builder.data[2].1, vec![(0.0, 1.0), (1.0, -0.5), (2.0, 3.0)]);
+}
+
+#[test]
+fn crisp_gridlines_are_snapped_but_data_is_not() {
+    let mut settings = Settings::default();
+    settings.theme.crisp_gridlines = true;
+    settings.theme.y_label_line_width = 1.0;
+    let chart = ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (3.0, 3.3), (10.0, 10.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let size = Size::new(400.0, 300.0);
+    let padded_area = chart.settings.padding.transform(Rectangle::new(Point::ORIGIN, size));
+    let margined_area = chart.settings.margin.transform(padded_area);
+
+    //A line of odd width goes through the pixel centers
+    let y_label = chart.y_label_geometry(size, padded_area, margined_area, YAxis::Primary, 150.2, "5");
+    assert!(y_label.lines.iter().all(|(from, to)| from.y == 150.5 && to.y == 150.5));
+    //The text stays where the value is
+    assert_eq!(y_label.text.position.y, 150.2);
+
+    let points = chart.points(margined_area.size());
+    assert!((points[0][1].y - 93.8).abs() < 1e-3);
+}
+
+#[test]
+fn merged_chunks_are_one_continuous_plot() {
+    let merged_settings = PlotSettings {
+        name: Some("merged".to_owned()),
+        ..Default::default()
+    };
+    let builder = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(10.0f32, 0.0f32)])
+        .add_data(PlotSettings::default(), vec![(2.0, 2.0), (3.0, 3.0)])
+        .add_data(PlotSettings::default(), vec![(0.0, 0.0), (1.0, 1.0)])
+        .merge_plots(&[2, 1], merged_settings);
+    assert_eq!(builder.data.len(), 2);
+    assert_eq!(builder.data[0].1, vec![(10.0, 0.0)]);
+    assert_eq!(builder.data[1].0.name, Some("merged".to_owned()));
+    assert_eq!(builder.data[1].1, vec![(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+}
+
+#[test]
+fn coincident_points_select_the_first_plot() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (5.0, 5.0)])
+        //Half a pixel to the right of the point of the first plot
+        .add_data(PlotSettings::default(), vec![(0.0f32, 10.0f32), (5.02, 5.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let margined_size = chart.margined_area(Size::new(400.0, 300.0)).size();
+    let points = chart.points(margined_size);
+    //The cursor is a little closer to the point of the second plot, but not clearly
+    assert_eq!(chart.find_selected_point(&points, Point::new(120.5, 70.0), margined_size), Some((0, 1)));
+}
+
+#[test]
+fn watermark_is_faded_in_its_corner() {
+    let settings = Settings {
+        watermark: Some(data::Watermark {
+            text: "draft".to_owned(),
+            corner: data::Corner::TopLeft,
+            color: Color::from_rgba(0.0, 0.0, 0.0, 0.8),
+            opacity: 0.5,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let chart = chart_0_10(settings);
+    let margined_area = chart.margined_area(Size::new(400.0, 300.0));
+    let watermark_text = chart.watermark_text(margined_area).unwrap();
+    assert_eq!(watermark_text.content, "draft");
+    assert_eq!(watermark_text.color.a, 0.4);
+    assert_eq!(watermark_text.position, Point::new(90.0, 90.0));
+    assert_eq!(watermark_text.horizontal_alignment, HorizontalAlignment::Left);
+    assert_eq!(watermark_text.vertical_alignment, VerticalAlignment::Top);
+}
+
+#[test]
+fn only_single_plot_charts_mark_extremes_by_default() {
+    let single = chart_0_10(Settings::default());
+    assert!(single.marks_extremes());
+
+    let multiple = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .add_data(PlotSettings::default(), vec![(0.0f32, 10.0f32), (10.0, 0.0)])
+        .calculate_min_max_values()
+        .build();
+    assert!(!multiple.marks_extremes());
+
+    let settings = Settings {
+        mark_extremes: Some(true),
+        ..Default::default()
+    };
+    let overridden = ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .add_data(PlotSettings::default(), vec![(0.0f32, 10.0f32), (10.0, 0.0)])
+        .calculate_min_max_values()
+        .build();
+    assert!(overridden.marks_extremes());
+}
+
+#[test]
+fn hover_is_sent_to_the_interaction_channel() {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .interaction_events(sender)
+        .build();
+    let bounds = Rectangle::new(Point::ORIGIN, Size::new(400.0, 300.0));
+    chart.update(
+        iced::canvas::Event::Mouse(iced::mouse::Event::CursorMoved { x: 200.0, y: 150.0 }),
+        bounds,
+        Cursor::Available(Point::new(200.0, 150.0)),
+    );
+    assert_eq!(receiver.try_recv().ok(), Some(InteractionEvent::Hover { x: 5.0, y: 5.0 }));
+}
+
+#[test]
+fn close_point_labels_are_hidden_or_nudged() {
+    let label_size = Size::new(30.0, 12.0);
+    let first = Point::new(100.0, 100.0);
+    let second = Point::new(110.0, 101.0);
+
+    let hiding = chart_0_10(Settings::default());
+    let first_area = hiding.point_label_area(first, label_size, 5.0, &[]).unwrap();
+    assert_eq!(first_area, Rectangle::new(Point::new(85.0, 83.0), label_size));
+    assert_eq!(hiding.point_label_area(second, label_size, 5.0, &[first_area]), None);
+
+    let settings = Settings {
+        point_label_overflow: LabelOverflow::Nudge,
+        ..Default::default()
+    };
+    let nudging = chart_0_10(settings);
+    let nudged_area = nudging.point_label_area(second, label_size, 5.0, &[first_area]);
+    assert_eq!(nudged_area, Some(Rectangle::new(Point::new(95.0, 106.0), label_size)));
+}
+
+#[test]
+fn x_labels_move_up_to_the_zero_line() {
+    let data = vec![(0.0f32, -10.0f32), (10.0, 10.0)];
+    let at_bottom = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), data.clone())
+        .calculate_min_max_values()
+        .build();
+    let settings = Settings {
+        x_axis_at_zero: true,
+        ..Default::default()
+    };
+    let at_zero = ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), data)
+        .calculate_min_max_values()
+        .build();
+    let size = Size::new(400.0, 300.0);
+    let padded_area = at_zero.settings.padding.transform(Rectangle::new(Point::ORIGIN, size));
+    let margined_area = at_zero.settings.margin.transform(padded_area);
+    assert_eq!(at_zero.x_axis_y(margined_area), Some(150.0));
+
+    let bottom_label = at_bottom.x_label_geometry(size, padded_area, margined_area, 200.0, "5");
+    let zero_label = at_zero.x_label_geometry(size, padded_area, margined_area, 200.0, "5");
+    //The tick mark ends at the zero line instead of the bottom of the plot
+    assert_eq!(bottom_label.lines[0].1, Point::new(200.0, 220.0));
+    assert_eq!(zero_label.lines[0].1, Point::new(200.0, 150.0));
+    assert_eq!(zero_label.text.position.y, bottom_label.text.position.y - 70.0);
+}
+
+#[test]
+fn resample_uniform_spaces_x_evenly() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (2.0, 4.0), (4.0, 0.0)])
+        .resample_uniform(0, 5, PlotSettings::default())
+        .calculate_min_max_values()
+        .build();
+    assert_eq!(chart.data.len(), 2);
+    assert_eq!(chart.data[1].1, vec![(0.0, 0.0), (1.0, 2.0), (2.0, 4.0), (3.0, 2.0), (4.0, 0.0)]);
+}
+
+#[test]
+fn nearest_gridlines_get_highlighted() {
+    let settings = Settings {
+        highlight_nearest_gridline: true,
+        ..Default::default()
+    };
+    let chart = chart_0_10(settings);
+    let margined_area = Rectangle::new(Point::new(80.0, 80.0), Size::new(240.0, 140.0));
+    let y_labels = vec![(100.0, "8".to_owned()), (150.0, "5".to_owned()), (200.0, "2".to_owned())];
+    let x_labels = vec![(100.0, "1".to_owned()), (200.0, "5".to_owned()), (300.0, "9".to_owned())];
+
+    let highlighted = chart.highlighted_gridlines(&y_labels, &x_labels, margined_area, Some(Point::new(240.0, 160.0)));
+    assert_eq!(highlighted, (Some(150.0), Some(200.0)));
+    //Nothing is highlighted when the cursor is outside of the plot
+    let outside = chart.highlighted_gridlines(&y_labels, &x_labels, margined_area, Some(Point::new(10.0, 160.0)));
+    assert_eq!(outside, (None, None));
+}
+
+#[test]
+fn normal_band_spans_the_width_between_its_values() {
+    let color = Color::from_rgba(0.0, 1.0, 0.0, 0.2);
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .normal_band(5.0, 20.0, color)
+        .build();
+    let margined_area = Rectangle::new(Point::new(80.0, 80.0), Size::new(240.0, 140.0));
+    //The high end is clamped to the top of the plot
+    let expected = Rectangle::new(Point::new(80.0, 80.0), Size::new(240.0, 70.0));
+    assert_eq!(chart.normal_band_area(margined_area), Some((expected, color)));
+}
+
+#[test]
+fn every_plot_uses_its_own_selection_distances() {
+    let wide = PlotSettings {
+        point_selection_distance: 30.0,
+        line_selection_distance: 20.0,
+        ..Default::default()
+    };
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 5.0f32), (5.0, 5.0), (10.0, 5.0)])
+        .add_data(wide, vec![(0.0f32, 3.0f32), (5.0, 3.0), (10.0, 3.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let margined_size = chart.margined_area(Size::new(400.0, 300.0)).size();
+    let points = chart.points(margined_size);
+    //12 pixels from the first plot and 16 from the second one, only the second one reaches that far
+    let cursor_position = Point::new(120.0, 82.0);
+    assert_eq!(chart.find_selected_point(&points, cursor_position, margined_size), Some((1, 1)));
+    assert_eq!(chart.find_selected_plot(&points, cursor_position), Some(1));
+    //Closer to the first plot, it wins again
+    let cursor_position = Point::new(120.0, 75.0);
+    assert_eq!(chart.find_selected_point(&points, cursor_position, margined_size), Some((0, 1)));
+}
+
+#[test]
+fn screen_points_are_the_drawn_points_moved_by_the_margins() {
+    let chart = chart_0_10(Settings::default());
+    let size = Size::new(400.0, 300.0);
+    let screen_points = chart.screen_points(size);
+    assert_eq!(screen_points, vec![(0, vec![Point::new(80.0, 220.0), Point::new(320.0, 80.0)])]);
+    //The points are drawn in a frame translated to the margined area
+    let margined_area = chart.margined_area(size);
+    let drawn: Vec<Point> = chart.points(margined_area.size())[0]
+        .iter()
+        .map(|point| Point::new(point.x + margined_area.x, point.y + margined_area.y))
+        .collect();
+    assert_eq!(screen_points[0].1, drawn);
+}
+
+#[test]
+fn pushed_points_fade_in_over_the_ticks() {
+    let settings = Settings {
+        fade_in_ticks: 2,
+        ..Default::default()
+    };
+    let mut chart = chart_0_10(settings);
+    chart.push_point(0, 5.0, 5.0);
+    assert_eq!(chart.fade_in_alpha(0, 0), 1.0);
+    let mut alphas = vec![chart.fade_in_alpha(0, 2)];
+    chart.tick();
+    alphas.push(chart.fade_in_alpha(0, 2));
+    chart.tick();
+    alphas.push(chart.fade_in_alpha(0, 2));
+    assert_eq!(alphas, vec![1.0 / 3.0, 2.0 / 3.0, 1.0]);
+}
+
+#[test]
+fn square_markers_can_be_selected() {
+    let plot_settings = PlotSettings {
+        theme: PlotThemeSettings {
+            marker: Marker::Square,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(plot_settings, vec![(0.0f32, 0.0f32), (5.0, 5.0), (10.0, 10.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let margined_size = chart.margined_area(Size::new(400.0, 300.0)).size();
+    let points = chart.points(margined_size);
+    //Near the corner of the square around (120, 70)
+    assert_eq!(chart.find_selected_point(&points, Point::new(124.0, 66.0), margined_size), Some((0, 1)));
+}
+
+#[test]
+fn display_transform_only_changes_the_label_texts() {
+    let data = vec![(0.0f32, 0.0f32), (10.0, 100.0)];
+    let celsius = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), data.clone())
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(100.0)
+        .build();
+    let fahrenheit = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), data)
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(100.0)
+        .y_display_transform(|celsius| celsius * 1.8 + 32.0)
+        .build();
+    let margined_area = Rectangle::new(Point::new(80.0, 80.0), Size::new(240.0, 140.0));
+    let celsius_labels = celsius.y_labels(margined_area);
+    let fahrenheit_labels = fahrenheit.y_labels(margined_area);
+    assert_eq!(
+        fahrenheit_labels,
+        vec![(220.0, "32.00".to_owned()), (150.0, "122.00".to_owned()), (80.0, "212.00".to_owned())],
+    );
+    //50 °C is still halfway up
+    let positions = |labels: &[(f32, String)]| labels.iter().map(|(y, _text)| *y).collect::<Vec<f32>>();
+    assert_eq!(positions(&fahrenheit_labels), positions(&celsius_labels));
+}
+
+#[test]
+fn jitter_is_stable_and_bounded() {
+    let plot_settings = PlotSettings {
+        jitter: 4.0,
+        ..Default::default()
+    };
+    let data: Vec<(f32, f32)> = (0..20).map(|i| (5.0, i as f32 / 2.0)).collect();
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(plot_settings, data)
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let size = Size::new(240.0, 140.0);
+    let points = chart.points(size);
+    assert_eq!(points, chart.points(size));
+    assert!(points[0].iter().all(|point| (point.x - 120.0).abs() <= 4.0));
+    //The points do spread out
+    assert!(points[0].iter().any(|point| point.x != points[0][0].x));
+}
+
+#[test]
+fn plots_over_the_total_cap_are_decimated() {
+    let settings = Settings {
+        max_total_points: Some(100),
+        ..Default::default()
+    };
+    let data: Vec<(f32, f32)> = (0..300).map(|i| (i as f32, (i % 7) as f32)).collect();
+    let chart = ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), data.clone())
+        .add_data(PlotSettings::default(), data[..100].to_vec())
+        .calculate_min_max_values()
+        .build();
+    let drawn_counts = |chart: &Chart<f32, f32, f32, f32>| -> Vec<(usize, bool)> {
+        chart.drawn_indices(&chart.points(Size::new(240.0, 140.0)))
+            .iter()
+            .map(|(drawn_indices, refinable)| (drawn_indices.len(), *refinable))
+            .collect()
+    };
+    //The cap is shared by the size of the plots, and it is never refined away
+    assert_eq!(drawn_counts(&chart), vec![(75, false), (25, false)]);
+    //Under the cap everything is drawn
+    let settings = Settings {
+        max_total_points: Some(100),
+        ..Default::default()
+    };
+    let small_chart = ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), data[..60].to_vec())
+        .add_data(PlotSettings::default(), data[..30].to_vec())
+        .calculate_min_max_values()
+        .build();
+    assert_eq!(drawn_counts(&small_chart), vec![(60, false), (30, false)]);
+}
+
+#[test]
+fn only_named_plots_get_legend_entries() {
+    let red = Color::from_rgb(1.0, 0.0, 0.0);
+    let blue = Color::from_rgb(0.0, 0.0, 1.0);
+    let named = |name: &str, line_color: Color| PlotSettings {
+        name: Some(name.to_owned()),
+        theme: PlotThemeSettings {
+            line_color,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(named("inside", red), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .add_data(PlotSettings::default(), vec![(0.0f32, 5.0f32), (10.0, 5.0)])
+        .add_data(named("outside", blue), vec![(0.0f32, 10.0f32), (10.0, 0.0)])
+        .calculate_min_max_values()
+        .build();
+    assert_eq!(chart.legend_entries(), vec![(red, "inside".to_owned()), (blue, "outside".to_owned())]);
+}
+
+#[test]
+fn ghost_is_drawn_under_the_decimated_line() {
+    let settings = Settings {
+        max_total_points: Some(50),
+        show_full_resolution_ghost: true,
+        ..Default::default()
+    };
+    let data: Vec<(f32, f32)> = (0..200).map(|i| (i as f32 / 20.0, (i % 10) as f32)).collect();
+    let chart = ChartBuilder::new(settings)
+        .add_data(PlotSettings::default(), data)
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let size = Size::new(240.0, 140.0);
+    let plot_area = Rectangle::new(Point::ORIGIN, size);
+    let points = chart.points(size);
+    //The same steps as `draw`: the capped indices, their points and the layers of the line through them
+    let (drawn_indices, _refinable) = &chart.drawn_indices(&points)[0];
+    let drawn_points: Vec<Point> = drawn_indices.iter().map(|point_index| points[0][*point_index]).collect();
+    assert_eq!(drawn_points.len(), 50);
+    match &chart.line_layers(&points[0], &drawn_points, plot_area)[..] {
+        [LineLayer::Ghost(segments), LineLayer::Line(runs)] => {
+            assert_eq!(segments.len(), 199);
+            assert_eq!(runs.len(), 1);
+            assert_eq!(runs[0].1.len(), 50);
+        }
+        line_layers => panic!("Expected the ghost under the line, got {:?}", line_layers),
+    }
+    //Without decimation there is nothing to show behind the line
+    assert_eq!(chart.line_layers(&points[0], &points[0], plot_area).len(), 1);
+}
+
+#[test]
+fn follow_tooltip_keeps_its_clearance_and_flips_at_the_edges() {
+    let chart = chart_0_10(Settings::default());
+    let size = Size::new(400.0, 300.0);
+    let margined_area = Rectangle::new(Point::new(80.0, 80.0), Size::new(240.0, 140.0));
+
+    let (box_area, corner) = chart.follow_tooltip_layout(size, margined_area, Point::new(150.0, 170.0), 10.0, "5.00");
+    //On the right and above, the leader line goes to the bottom left corner
+    assert_eq!(box_area.x, 160.0);
+    assert_eq!(box_area.y + box_area.height, 160.0);
+    assert_eq!(corner, Point::new(160.0, 160.0));
+
+    let (box_area, corner) = chart.follow_tooltip_layout(size, margined_area, Point::new(315.0, 85.0), 10.0, "5.00");
+    //No room on the right or above, the leader line goes to the top right corner
+    assert_eq!(box_area.x + box_area.width, 305.0);
+    assert_eq!(box_area.y, 95.0);
+    assert_eq!(corner, Point::new(305.0, 95.0));
+}
+
+#[test]
+fn identity_line_points_have_equal_x_and_y() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 2.0f32), (10.0, 20.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(2.0)
+        .max_y_value(20.0)
+        .build();
+    let size = Size::new(240.0, 140.0);
+    let points = chart.identity_points(size);
+    assert_eq!(points.len(), 65);
+    let values: Vec<(f32, f32)> = points
+        .iter()
+        .map(|point| (chart.unmap_x(point.x, size.width).unwrap(), chart.unmap_y(point.y, size.height).unwrap()))
+        .collect();
+    assert!(values.iter().all(|(x, y)| (x - y).abs() < 1e-3));
+    //The line only goes over the range shared by both axes
+    assert!((values[0].0 - 2.0).abs() < 1e-3);
+    assert!((values[64].0 - 10.0).abs() < 1e-3);
+}
+
+#[test]
+fn expanding_bounds_make_room_for_pushed_points() {
+    let settings = Settings {
+        streaming_bounds: StreamingBounds::Expand { headroom: 0.1 },
+        ..Default::default()
+    };
+    let mut chart = chart_0_10(settings);
+    chart.push_point(0, 5.0, 5.0);
+    assert_eq!(chart.x_bounds(), (&0.0, &10.0));
+    chart.push_point(0, 20.0, 5.0);
+    //Only the side the point is on grows, with 10% of headroom
+    assert_eq!(chart.x_bounds(), (&0.0, &22.0));
+    assert_eq!(chart.y_bounds(), (&0.0, &10.0));
+}
+
+#[test]
+fn bool_y_values_are_labeled_with_their_names() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, true), (10.0, true)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .y_bool_labels("Off".to_owned(), "On".to_owned())
+        .build();
+    let margined_area = Rectangle::new(Point::new(80.0, 80.0), Size::new(240.0, 140.0));
+    //Both states are labeled, at the bottom and at the top, though the data is always on
+    assert_eq!(chart.y_labels(margined_area), vec![(220.0, "Off".to_owned()), (80.0, "On".to_owned())]);
+}
+
+#[test]
+fn data_pixel_bounds_enclose_the_points() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(2.0f32, 1.0f32), (5.0, 9.0)])
+        .add_data(PlotSettings::default(), vec![(8.0f32, 4.0f32)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let bounds = chart.data_pixel_bounds(Size::new(400.0, 300.0)).unwrap();
+    //From (2, 9) at the top left to (8, 1) at the bottom right
+    assert_eq!(bounds, Rectangle::new(Point::new(128.0, 94.0), Size::new(144.0, 112.0)));
+    let empty = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), Vec::<(f32, f32)>::new())
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    assert_eq!(empty.data_pixel_bounds(Size::new(400.0, 300.0)), None);
+}
+
+#[test]
+fn gridlines_go_where_the_grid_layer_says() {
+    assert_eq!(
+        layer_order(GridLayer::BelowMarginedBackground),
+        [Layer::Backgrounds, Layer::Grid, Layer::MarginedBackground, Layer::Data],
+    );
+    assert!(grid_follows(GridLayer::BelowMarginedBackground, Layer::Backgrounds));
+    assert!(!grid_follows(GridLayer::BelowMarginedBackground, Layer::MarginedBackground));
+
+    assert!(grid_follows(GridLayer::BelowData, Layer::MarginedBackground));
+    assert!(!grid_follows(GridLayer::BelowData, Layer::Data));
+
+    assert_eq!(
+        layer_order(GridLayer::AboveData),
+        [Layer::Backgrounds, Layer::MarginedBackground, Layer::Data, Layer::Grid],
+    );
+    assert!(grid_follows(GridLayer::AboveData, Layer::Data));
+    assert!(!grid_follows(GridLayer::AboveData, Layer::Backgrounds));
+}
+
+#[test]
+fn a_gap_splits_the_line_in_two_runs() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (2.0, 2.0), (4.0, f32::NAN), (6.0, 6.0), (8.0, 8.0), (10.0, 10.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .build();
+    let points = chart.points(Size::new(240.0, 140.0));
+    let runs = gap_runs(&points[0]);
+    assert_eq!(runs.len(), 2);
+    assert_eq!((runs[0].0, runs[0].1.len()), (0, 2));
+    assert_eq!((runs[1].0, runs[1].1.len()), (3, 3));
+}
+
+#[test]
+fn close_u64_values_far_from_zero_map_apart() {
+    let base = 1_600_000_000_000_000_000u64;
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(base, 0.0f32), (base + 1_000, 10.0)])
+        .calculate_min_max_values()
+        .build();
+    let x1 = chart.map_x(&(base + 1), 1000.0);
+    let x2 = chart.map_x(&(base + 2), 1000.0);
+    assert_eq!((x1, x2), (1.0, 2.0));
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn decimal_prices_are_charted_exactly() {
+    use rust_decimal::Decimal;
+
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, Decimal::new(1999, 2)), (1.0, Decimal::new(2450, 2)), (2.0, Decimal::new(2125, 2))])
+        .calculate_min_max_values()
+        .build();
+    assert_eq!(chart.y_bounds(), (&Decimal::new(1999, 2), &Decimal::new(2450, 2)));
+    assert_eq!(chart.map_y(&Decimal::new(1999, 2), 140.0), 140.0);
+    assert_eq!(chart.map_y(&Decimal::new(2450, 2), 140.0), 0.0);
+    let margined_area = Rectangle::new(Point::new(80.0, 80.0), Size::new(240.0, 140.0));
+    let y_labels = chart.y_labels(margined_area);
+    assert_eq!(y_labels.first(), Some(&(220.0, "19.99".to_owned())));
+    assert_eq!(y_labels.last(), Some(&(80.0, "24.50".to_owned())));
+}
+
+#[test]
+fn legend_moves_to_its_corner() {
+    let plot_settings = PlotSettings {
+        name: Some("sensor".to_owned()),
+        ..Default::default()
+    };
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(plot_settings, vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .calculate_min_max_values()
+        .build();
+    let margined_area = Rectangle::new(Point::new(80.0, 80.0), Size::new(240.0, 140.0));
+    let entries = chart.legend_entries();
+    let area_at = |corner: data::Corner| {
+        let legend = LegendSettings {
+            corner,
+            ..Default::default()
+        };
+        chart.legend_area(margined_area, legend, &entries)
+    };
+    let top_left = area_at(data::Corner::TopLeft);
+    let (width, height) = (top_left.width, top_left.height);
+    assert_eq!(top_left.position(), Point::new(90.0, 90.0));
+    assert_eq!(area_at(data::Corner::TopRight).position(), Point::new(310.0 - width, 90.0));
+    assert_eq!(area_at(data::Corner::BottomLeft).position(), Point::new(90.0, 210.0 - height));
+    assert_eq!(area_at(data::Corner::BottomRight).position(), Point::new(310.0 - width, 210.0 - height));
+}
+
+#[test]
+fn markers_outside_of_tight_bounds_are_not_drawn() {
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, -5.0f32), (5.0, 5.0), (6.0, 1.0), (8.0, -1.0), (10.0, -5.0)])
+        .min_x_value(3.0)
+        .max_x_value(10.0)
+        .min_y_value(-2.0)
+        .max_y_value(2.0)
+        .mark_crossings(0.0)
+        .build();
+    let margined_size = chart.margined_area(Size::new(400.0, 300.0)).size();
+    let plot_area = Rectangle::new(Point::ORIGIN, margined_size);
+    let points = chart.points(margined_size);
+    //The line crosses zero at x = 2.5 (left of the window) and at x = 7
+    let crossings = chart.crossing_markers(YAxis::Primary, &points[0], plot_area);
+    assert_eq!(crossings.len(), 1);
+    assert!((chart.unmap_x(crossings[0].x, margined_size.width).unwrap() - 7.0).abs() < 1e-3);
+    //The highest and the lowest points (5 and -5) are outside of the y window
+    let ((highest_index, _highest), (lowest_index, _lowest)) = extreme_points(&points[0], plot_area).unwrap();
+    assert_eq!((highest_index, lowest_index), (2, 3));
+    //Points outside can't be selected, so there is no selected marker for them
+    assert_eq!(chart.find_selected_point(&points, points[0][1], margined_size), None);
+}
+
+#[test]
+fn crossings_are_marked_at_the_interpolated_x_on_the_axis_of_the_plot() {
+    let secondary = PlotSettings {
+        axis: YAxis::Secondary,
+        ..Default::default()
+    };
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, -2.0f32), (4.0, 2.0), (10.0, 2.0)])
+        .add_data(secondary, vec![(0.0f32, -10.0f32), (4.0, 10.0), (10.0, 10.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(-5.0)
+        .max_y_value(5.0)
+        .secondary_y_values(-10.0, 30.0)
+        .mark_crossings(0.0)
+        .build();
+    let margined_size = chart.margined_area(Size::new(400.0, 300.0)).size();
+    let plot_area = Rectangle::new(Point::ORIGIN, margined_size);
+    let points = chart.points(margined_size);
+    //Both plots cross zero at x = 2, halfway up the primary range and a quarter up the secondary one
+    assert_eq!(chart.crossing_markers(YAxis::Primary, &points[0], plot_area), vec![Point::new(48.0, 70.0)]);
+    assert_eq!(chart.crossing_markers(YAxis::Secondary, &points[1], plot_area), vec![Point::new(48.0, 105.0)]);
+}
+
+#[cfg(feature = "export")]
+#[test]
+fn svg_has_markers_dashes_and_secondary_labels() {
+    let dashed_squares = PlotSettings {
+        theme: PlotThemeSettings {
+            marker: Marker::Square,
+            line_style: LineStyle::Dashed { dash: 6.0, gap: 3.0 },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let secondary = PlotSettings {
+        axis: YAxis::Secondary,
+        ..Default::default()
+    };
+    let chart = ChartBuilder::new(Settings::default())
+        .add_data(dashed_squares, vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .add_data(secondary, vec![(0.0f32, 100.0f32), (10.0, 200.0)])
+        .min_x_value(0.0)
+        .max_x_value(10.0)
+        .min_y_value(0.0)
+        .max_y_value(10.0)
+        .secondary_y_values(100.0, 200.0)
+        .build();
+    let svg = chart.render_to_svg(Size::new(400.0, 300.0));
+    assert!(svg.contains(r#"stroke-dasharray="6 3""#));
+    //The squares of the first plot, the circles of the second one
+    assert_eq!(svg.matches("<polygon").count(), 2);
+    assert_eq!(svg.matches("<circle").count(), 2);
+    assert!(svg.contains(">200.00</text>"));
+}
+
+#[test]
+#[should_panic(expected = "There is no finite y value on the secondary axis!")]
+fn secondary_y_values_need_a_finite_value() {
+    let secondary = PlotSettings {
+        axis: YAxis::Secondary,
+        ..Default::default()
+    };
+    ChartBuilder::new(Settings::default())
+        .add_data(PlotSettings::default(), vec![(0.0f32, 0.0f32), (10.0, 10.0)])
+        .add_data(secondary, vec![(0.0f32, f32::NAN), (10.0, f32::INFINITY)])
+        .calculate_secondary_y_values();
+}
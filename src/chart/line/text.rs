@@ -0,0 +1,56 @@
+//The canvas can't measure text, so the width is estimated from the average glyph width
+const AVERAGE_GLYPH_WIDTH: f32 = 0.6; //Relative to the text size
+
+pub fn estimate_width(text: &str, size: f32) -> f32 {
+    text.lines()
+        .map(|line| line.chars().count() as f32 * size * AVERAGE_GLYPH_WIDTH)
+        .fold(0.0, f32::max)
+}
+
+//Breaks the text into lines no wider than `max_width`, keeping the existing line breaks.
+//Words are kept whole unless a single word doesn't fit.
+pub fn wrap(text: &str, size: f32, max_width: f32) -> String {
+    let max_chars = ((max_width / (size * AVERAGE_GLYPH_WIDTH)).floor() as usize).max(1);
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let mut current = String::new();
+        for word in line.split(' ') {
+            let mut word: Vec<char> = word.chars().collect();
+            //A word too long for a line of its own is split
+            while word.len() > max_chars {
+                if !current.is_empty() {
+                    lines.push(std::mem::take(&mut current));
+                }
+                lines.push(word.drain(..max_chars).collect());
+            }
+            let word: String = word.into_iter().collect();
+            let current_len = current.chars().count();
+            if current.is_empty() {
+                current = word;
+            } else if current_len + 1 + word.chars().count() <= max_chars {
+                current.push(' ');
+                current.push_str(&word);
+            } else {
+                lines.push(std::mem::replace(&mut current, word));
+            }
+        }
+        lines.push(current);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_text_wraps_within_max_width() {
+        let description = "a rather long description of a point which would run off the canvas";
+        let wrapped = wrap(description, 10.0, 120.0);
+        assert!(wrapped.lines().count() > 1);
+        assert!(wrapped.lines().all(|line| estimate_width(line, 10.0) <= 120.0));
+        assert_eq!(wrapped.replace('\n', " "), description);
+        //Words longer than a whole line are split
+        assert_eq!(wrap("abcdefghijklmnopqrstuvwxyz", 10.0, 60.0), "abcdefghij\nklmnopqrst\nuvwxyz");
+    }
+}
@@ -1,4 +1,4 @@
-use iced::Point;
+use iced::{Color, Point, Rectangle};
 
 //https://stackoverflow.com/a/12931306
 //Maps `value` from interval `[a1;b1]` to the same relative position in `[a2;b2]`
@@ -50,3 +50,415 @@ pub fn point_to_interval_distance(point: Point, path_point1: Point, path_point2:
     let dy = y - yy;
     return (dx * dx + dy * dy).sqrt();
 }
+
+//Picks at most `max` evenly spread indices of `0..len`, always keeping the first and the last one
+pub fn decimate_indices(len: usize, max: usize) -> Vec<usize> {
+    if len <= max {
+        return (0..len).collect();
+    }
+    match max {
+        0 => Vec::new(),
+        1 => vec![0],
+        _ => {
+            let step = (len - 1) as f32 / (max - 1) as f32;
+            let mut result: Vec<usize> = (0..max).map(|i| ((i as f32 * step).round() as usize).min(len - 1)).collect();
+            result.dedup();
+            result
+        }
+    }
+}
+
+//Largest-Triangle-Three-Buckets: picks at most `target` indices of `points` which keep the shape of the line,
+//always keeping the first and the last one
+pub fn lttb_indices(points: &[(f32, f32)], target: usize) -> Vec<usize> {
+    let len = points.len();
+    if len <= target || target < 3 {
+        return decimate_indices(len, target);
+    }
+    let bucket_size = (len - 2) as f32 / (target - 2) as f32;
+    //Bounds of the bucket `i` of the points between the first and the last one
+    let bucket = |i: usize| {
+        let start = (i as f32 * bucket_size) as usize + 1;
+        let end = (((i + 1) as f32 * bucket_size) as usize + 1).min(len - 1);
+        (start, end.max(start + 1))
+    };
+    let mut result = Vec::with_capacity(target);
+    result.push(0);
+    let mut previous = points[0];
+    for i in 0..target - 2 {
+        let (start, end) = bucket(i);
+        //The third vertex is the average of the next bucket, the last point for the last bucket
+        let (next_x, next_y) = if i + 1 < target - 2 {
+            let (next_start, next_end) = bucket(i + 1);
+            let next = &points[next_start..next_end];
+            let count = next.len() as f32;
+            (
+                next.iter().map(|(x, _y)| x).sum::<f32>() / count,
+                next.iter().map(|(_x, y)| y).sum::<f32>() / count,
+            )
+        } else {
+            points[len - 1]
+        };
+        let (chosen, _area) = (start..end)
+            .map(|index| {
+                let (x, y) = points[index];
+                let area = ((previous.0 - next_x) * (y - previous.1) - (previous.0 - x) * (next_y - previous.1)).abs();
+                (index, area)
+            })
+            .fold((start, -1.0), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+        result.push(chosen);
+        previous = points[chosen];
+    }
+    result.push(len - 1);
+    result
+}
+
+//Linear interpolation between two colors, `t` in `[0;1]`
+pub fn interpolate_color(from: Color, to: Color, t: f32) -> Color {
+    Color {
+        r: from.r + (to.r - from.r) * t,
+        g: from.g + (to.g - from.g) * t,
+        b: from.b + (to.b - from.b) * t,
+        a: from.a + (to.a - from.a) * t,
+    }
+}
+
+//Collapses consecutive points inside of the same pixel column into a vertical bar from the lowest to the highest one.
+//Returns `None` when no column has more than one point.
+pub fn aggregate_columns(points: &[Point]) -> Option<Vec<Point>> {
+    let mut result = Vec::new();
+    let mut aggregated = false;
+    let mut i = 0;
+    while i < points.len() {
+        let column = points[i].x.floor();
+        let mut min_y = points[i].y;
+        let mut max_y = points[i].y;
+        let mut j = i + 1;
+        while j < points.len() && points[j].x.floor() == column {
+            min_y = min_y.min(points[j].y);
+            max_y = max_y.max(points[j].y);
+            j += 1;
+        }
+        if j - i > 1 {
+            aggregated = true;
+            result.push(Point::new(column + 0.5, min_y));
+            result.push(Point::new(column + 0.5, max_y));
+        } else {
+            result.push(points[i]);
+        }
+        i = j;
+    }
+    aggregated.then_some(result)
+}
+
+//Confidence ellipse of 2D points, from their mean and covariance.
+//Returns the center, the semi-axes and the rotation (radians) of the first axis,
+//`None` for fewer than two points. `confidence` is in `(0;1)`, e.g. 0.95.
+pub fn confidence_ellipse(points: &[(f32, f32)], confidence: f32) -> Option<((f32, f32), (f32, f32), f32)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f32;
+    let mean_x = points.iter().map(|(x, _y)| x).sum::<f32>() / n;
+    let mean_y = points.iter().map(|(_x, y)| y).sum::<f32>() / n;
+    let (mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0);
+    for (x, y) in points {
+        let dx = x - mean_x;
+        let dy = y - mean_y;
+        sxx += dx * dx;
+        syy += dy * dy;
+        sxy += dx * dy;
+    }
+    let (sxx, syy, sxy) = (sxx / (n - 1.0), syy / (n - 1.0), sxy / (n - 1.0));
+    //Eigenvalues of the 2x2 covariance matrix
+    let half_trace = (sxx + syy) / 2.0;
+    let root = (((sxx - syy) / 2.0).powi(2) + sxy * sxy).sqrt();
+    let lambda1 = half_trace + root;
+    let lambda2 = (half_trace - root).max(0.0);
+    let angle = 0.5 * (2.0 * sxy).atan2(sxx - syy);
+    //Chi-squared quantile with two degrees of freedom
+    let scale = -2.0 * (1.0 - confidence.clamp(0.0, 0.999_999)).ln();
+    Some((
+        (mean_x, mean_y),
+        ((scale * lambda1).sqrt(), (scale * lambda2).sqrt()),
+        angle,
+    ))
+}
+
+//Points where the polyline crosses the horizontal line at `y`, found by linear interpolation.
+//Segments which only touch the line with their end are counted once.
+pub fn crossings(points: &[Point], y: f32) -> Vec<Point> {
+    points
+        .windows(2)
+        .filter_map(|slice| {
+            let p1 = slice[0];
+            let p2 = slice[1];
+            let d1 = p1.y - y;
+            let d2 = p2.y - y;
+            if d1 == 0.0 {
+                Some(p1)
+            } else if d1 * d2 < 0.0 {
+                let t = d1 / (d1 - d2);
+                Some(Point::new(p1.x + t * (p2.x - p1.x), y))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+//The part of the segment inside of the area (edges included), `None` if it misses the area (Liang-Barsky).
+//Ends which don't have to move are returned as they are.
+pub fn clip_segment(p1: Point, p2: Point, area: Rectangle) -> Option<(Point, Point)> {
+    let dx = p2.x - p1.x;
+    let dy = p2.y - p1.y;
+    let mut t0: f32 = 0.0;
+    let mut t1: f32 = 1.0;
+    //Every edge as the change of the distance to it along the segment and the distance of `p1` to it
+    let edges = [
+        (-dx, p1.x - area.x),
+        (dx, area.x + area.width - p1.x),
+        (-dy, p1.y - area.y),
+        (dy, area.y + area.height - p1.y),
+    ];
+    for (p, q) in edges.iter() {
+        if *p == 0.0 {
+            //Parallel to the edge, either fully inside of it or fully outside
+            if *q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if *p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    let at = |t: f32| match t {
+        t if t <= 0.0 => p1,
+        t if t >= 1.0 => p2,
+        t => Point::new(p1.x + dx * t, p1.y + dy * t),
+    };
+    Some((at(t0), at(t1)))
+}
+
+//The pieces of the polyline inside of the area, with the index of the segment they start in.
+//Points outside of the area are dropped, segments crossing its edge end at the crossing.
+pub fn clip_polyline(points: &[Point], area: Rectangle) -> Vec<(usize, Vec<Point>)> {
+    if points.len() == 1 {
+        return if area.contains(points[0]) { vec![(0, points.to_vec())] } else { Vec::new() };
+    }
+    let mut pieces = Vec::new();
+    let mut current_opt: Option<(usize, Vec<Point>)> = None;
+    for (index, slice) in points.windows(2).enumerate() {
+        match clip_segment(slice[0], slice[1], area) {
+            Some((p1, p2)) => {
+                match &mut current_opt {
+                    Some((_start, piece)) if p1 == slice[0] => piece.push(p2),
+                    _ => {
+                        pieces.extend(current_opt.take());
+                        current_opt = Some((index, vec![p1, p2]));
+                    }
+                }
+                //The segment leaves the area, the next one starts a new piece
+                if p2 != slice[1] {
+                    pieces.extend(current_opt.take());
+                }
+            }
+            None => pieces.extend(current_opt.take()),
+        }
+    }
+    pieces.extend(current_opt.take());
+    pieces
+}
+
+//Moves a coordinate of a straight line so the line covers whole pixels:
+//lines of odd width go through the pixel centers, lines of even width along the pixel edges
+pub fn snap_to_pixel(coord: f32, line_width: f32) -> f32 {
+    if line_width.round() as i64 % 2 == 1 {
+        coord.floor() + 0.5
+    } else {
+        coord.round()
+    }
+}
+
+//Coordinates splitting every gap between the sorted `major` coordinates into equal parts
+//as close to `spacing` as possible, without the major coordinates themselves
+pub fn minor_coords(major: &[f32], spacing: f32) -> Vec<f32> {
+    if spacing <= 0.0 {
+        return Vec::new();
+    }
+    major
+        .windows(2)
+        .flat_map(|slice| {
+            let (from, to) = (slice[0], slice[1]);
+            let parts = ((to - from).abs() / spacing).round().max(1.0) as usize;
+            (1..parts).map(move |i| from + (to - from) * i as f32 / parts as f32)
+        })
+        .collect()
+}
+
+//Splits the polyline into dashes of length `dash` separated by gaps of length `gap`.
+//The pattern runs along the whole polyline instead of restarting at every vertex, so a dash may bend around one.
+pub fn dash_polyline(points: &[Point], dash: f32, gap: f32) -> Vec<Vec<Point>> {
+    if dash <= 0.0 || points.len() < 2 {
+        return Vec::new();
+    }
+    if gap <= 0.0 {
+        return vec![points.to_vec()];
+    }
+    let mut dashes = Vec::new();
+    let mut current: Vec<Point> = Vec::new();
+    let mut drawing = true;
+    let mut remaining = dash; //Length left of the current dash or gap
+    for slice in points.windows(2) {
+        let p1 = slice[0];
+        let p2 = slice[1];
+        let length = p1.distance(p2);
+        if drawing && current.is_empty() {
+            current.push(p1);
+        }
+        let mut travelled = 0.0;
+        while length - travelled > remaining {
+            travelled += remaining;
+            let t = travelled / length;
+            current.push(Point::new(p1.x + (p2.x - p1.x) * t, p1.y + (p2.y - p1.y) * t));
+            if drawing {
+                dashes.push(std::mem::take(&mut current));
+                remaining = gap;
+            } else {
+                remaining = dash;
+            }
+            drawing = !drawing;
+        }
+        remaining -= length - travelled;
+        if drawing {
+            current.push(p2);
+        }
+    }
+    if current.len() >= 2 {
+        dashes.push(current);
+    }
+    dashes
+}
+
+//Points every `spacing` along the polyline, starting with its first point
+pub fn points_along(points: &[Point], spacing: f32) -> Vec<Point> {
+    let mut result = Vec::new();
+    if spacing <= 0.0 {
+        return result;
+    }
+    let mut next = 0.0; //Distance from the start of the current segment to the next point
+    for slice in points.windows(2) {
+        let p1 = slice[0];
+        let p2 = slice[1];
+        let length = p1.distance(p2);
+        while next <= length {
+            let t = if length > 0.0 { next / length } else { 0.0 };
+            result.push(Point::new(p1.x + (p2.x - p1.x) * t, p1.y + (p2.y - p1.y) * t));
+            next += spacing;
+        }
+        next -= length;
+    }
+    result
+}
+
+//A pseudo random value in [-1; 1] which is always the same for the same seed (splitmix64)
+pub fn seeded_unit(seed: u64) -> f32 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+}
+
+//Bezier control points of every segment of a Catmull-Rom spline through the points.
+//The x of the control points stays between the x of the segment ends, so the curve never goes back in x.
+pub fn catmull_rom_controls(points: &[Point]) -> Vec<(Point, Point)> {
+    let last = points.len().saturating_sub(1);
+    (0..last)
+        .map(|i| {
+            let p0 = points[i.saturating_sub(1)];
+            let p1 = points[i];
+            let p2 = points[i + 1];
+            let p3 = points[(i + 2).min(last)];
+            let (lo, hi) = if p1.x <= p2.x { (p1.x, p2.x) } else { (p2.x, p1.x) };
+            let c1 = Point::new((p1.x + (p2.x - p0.x) / 6.0).max(lo).min(hi), p1.y + (p2.y - p0.y) / 6.0);
+            let c2 = Point::new((p2.x - (p3.x - p1.x) / 6.0).max(lo).min(hi), p2.y - (p3.y - p1.y) / 6.0);
+            (c1, c2)
+        })
+        .collect()
+}
+
+//The Catmull-Rom spline through the points as a polyline with `steps` segments per original segment
+pub fn flatten_catmull_rom(points: &[Point], steps: usize) -> Vec<Point> {
+    let mut result: Vec<Point> = points.iter().take(1).copied().collect();
+    for (i, (c1, c2)) in catmull_rom_controls(points).into_iter().enumerate() {
+        let p1 = points[i];
+        let p2 = points[i + 1];
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+            result.push(Point::new(
+                a * p1.x + b * c1.x + c * c2.x + d * p2.x,
+                a * p1.y + b * c1.y + c * c2.y + d * p2.y,
+            ));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_columns_gives_min_max_bars() {
+        let points = [
+            Point::new(10.1, 5.0),
+            Point::new(10.4, 1.0),
+            Point::new(10.8, 9.0),
+            Point::new(12.0, 3.0),
+        ];
+        assert_eq!(
+            aggregate_columns(&points),
+            Some(vec![Point::new(10.5, 1.0), Point::new(10.5, 9.0), Point::new(12.0, 3.0)]),
+        );
+        assert_eq!(aggregate_columns(&[Point::new(1.0, 1.0), Point::new(2.0, 2.0)]), None);
+    }
+
+    #[test]
+    fn confidence_ellipse_is_centered_on_the_mean() {
+        let points = [(1.0, 2.0), (3.0, 1.0), (2.0, 6.0), (6.0, 3.0)];
+        let ((center_x, center_y), (semi_axis1, semi_axis2), _angle) = confidence_ellipse(&points, 0.95).unwrap();
+        assert!((center_x - 3.0).abs() < 1e-5);
+        assert!((center_y - 3.0).abs() < 1e-5);
+        assert!(semi_axis1 > 0.0 && semi_axis2 > 0.0);
+        assert_eq!(confidence_ellipse(&points[..1], 0.95), None);
+    }
+
+    #[test]
+    fn crossing_is_interpolated() {
+        let points = [Point::new(0.0, -2.0), Point::new(4.0, 6.0), Point::new(8.0, 6.0)];
+        assert_eq!(crossings(&points, 0.0), vec![Point::new(1.0, 0.0)]);
+        //Touching the line with a segment end counts once
+        let points = [Point::new(0.0, -2.0), Point::new(2.0, 0.0), Point::new(4.0, 2.0)];
+        assert_eq!(crossings(&points, 0.0), vec![Point::new(2.0, 0.0)]);
+    }
+
+    #[test]
+    fn minor_gridline_count_scales_with_width() {
+        let narrow = minor_coords(&[0.0, 100.0], 10.0);
+        let wide = minor_coords(&[0.0, 200.0], 10.0);
+        assert_eq!(narrow.len(), 9);
+        assert_eq!(wide.len(), 19);
+        assert_eq!(narrow[0], 10.0);
+        assert!(narrow.iter().all(|coord| *coord > 0.0 && *coord < 100.0));
+    }
+}